@@ -24,6 +24,12 @@ mod open_payroll {
     const MAX_BENEFICIARIES: usize = 100;
     const MAX_MULTIPLIERS: usize = 10;
 
+    // Establish the maximum length in bytes of the names stored in the contract
+    const MAX_STRING_LEN: usize = 64;
+
+    // Precision of the multipliers, a multiplier equal to the base pays the full base payment
+    const MULTIPLIER_BASE: Multiplier = 100;
+
     //----------------------------------------------------------------------------------------
     // Errors
     //----------------------------------------------------------------------------------------
@@ -65,6 +71,8 @@ mod open_payroll {
         DuplicatedBeneficiaries,
         /// The multiplier is not expired yet
         MultiplierNotExpired,
+        /// The multiplier is deactivated and its validity already ended
+        MultiplierExpired,
         /// The maximum number of beneficiaries is exceeded
         MaxBeneficiariesExceeded,
         /// The maximum number of multipliers is exceeded
@@ -73,12 +81,50 @@ mod open_payroll {
         AccountAlreadyExists,
         /// The multiplier ID overflowed
         MultiplierIdOverflow,
+        /// The string is longer than the maximum allowed
+        StringTooLong,
+        /// The beneficiary is frozen and can not claim
+        BeneficiaryFrozen,
+        /// The multiplier value is above the maximum ratio of the multiplier base
+        MultiplierValueTooLarge,
+        /// The claim would leave the treasury below the unclaimed payments of the other beneficiaries
+        WouldStrandCommittedPayments,
+        /// The contract is not in the closing state
+        ContractNotClosing,
+        /// The ownership proposal can not be accepted anymore
+        ProposalExpired,
+        /// The action would leave the contract without active multipliers
+        WouldLeaveNoMultipliers,
+        /// The amount overflowed
+        AmountOverflow,
+        /// The beneficiary already claimed in the current period
+        AlreadyClaimedThisPeriod,
+        /// The caller is not the beneficiary, the owner or an approved delegate
+        NotAllowedToClaim,
+        /// The claim is made after the claim window of the period
+        OutsideClaimWindow,
+        /// The recipient of the payment is not in the payout allowlist
+        RecipientNotAllowed,
+        /// The contract is frozen, only unfreeze_all can be called
+        ContractFrozen,
     }
 
     //----------------------------------------------------------------------------------------
     // Events
     //----------------------------------------------------------------------------------------
 
+    /// Emitted when the contract is created
+    #[ink(event)]
+    pub struct ContractInitialized {
+        #[ink(topic)]
+        owner: AccountId,
+        base_payment: Balance,
+        periodicity: u32,
+        initial_block: BlockNumber,
+        multiplier_count: u32,
+        beneficiary_count: u32,
+    }
+
     /// Emitted when a beneficiary claims their payment
     #[ink(event)]
     pub struct Claimed {
@@ -87,6 +133,8 @@ mod open_payroll {
         amount: Balance,
         total_payment: Balance,
         claiming_period_block: BlockNumber,
+        treasury_after: Balance,
+        memo: Option<String>,
     }
 
     /// Emitted when a multiplier is deactivated
@@ -105,6 +153,46 @@ mod open_payroll {
         valid_until_block: BlockNumber,
     }
 
+    /// Emitted when dangling multipliers are removed from a beneficiary
+    #[ink(event)]
+    pub struct BeneficiaryPruned {
+        #[ink(topic)]
+        account_id: AccountId,
+        removed_ids: Vec<MultiplierId>,
+    }
+
+    /// Emitted when the owner grants a bonus to a beneficiary
+    #[ink(event)]
+    pub struct BonusGranted {
+        #[ink(topic)]
+        account_id: AccountId,
+        amount: Balance,
+    }
+
+    /// Emitted when a beneficiary is frozen or unfrozen
+    #[ink(event)]
+    pub struct BeneficiaryFreezeChanged {
+        #[ink(topic)]
+        account_id: AccountId,
+        frozen: bool,
+    }
+
+    /// Emitted when the whole contract is frozen or unfrozen
+    #[ink(event)]
+    pub struct ContractFreezeChanged {
+        frozen: bool,
+    }
+
+    /// Emitted when a beneficiary approves or revokes a delegate
+    #[ink(event)]
+    pub struct DelegateApprovalChanged {
+        #[ink(topic)]
+        beneficiary: AccountId,
+        #[ink(topic)]
+        delegate: AccountId,
+        approved: bool,
+    }
+
     /// Emiited when the ownership of the contract is transferred
     #[ink(event)]
     pub struct OwnershipProposed {
@@ -159,9 +247,25 @@ mod open_payroll {
         periodicity: u32,
     }
 
+    /// Emitted when the treasury is moved to a new payroll contract
+    #[ink(event)]
+    pub struct RolledOver {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    /// Emitted when the owner withdraws the whole treasury as a last resort
+    #[ink(event)]
+    pub struct EmergencyWithdraw {
+        amount: Balance,
+    }
+
     /// Emitted when the contract is paused
     #[ink(event)]
-    pub struct Paused {}
+    pub struct Paused {
+        reason: Option<String>,
+    }
 
     /// Emitted when the contract is resumed
     #[ink(event)]
@@ -187,14 +291,28 @@ mod open_payroll {
         }
     }
 
-    /// Beneficiary structure containing the account id, the multipliers, the unclaimed payments, and the last updated period block
+    /// Beneficiary structure containing the account id, the multipliers, the cached sum of the active multipliers,
+    /// the unclaimed payments, the last updated period block, the periodicity override, the frozen flag
+    /// and the block in which it was added
     #[derive(scale::Encode, scale::Decode, Eq, PartialEq, Debug, Clone)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout,))]
     pub struct Beneficiary {
         account_id: AccountId,
         multipliers: BTreeMap<MultiplierId, Multiplier>,
+        // Sum of the multipliers, None while any of them is deactivated or deleted
+        // Recomputed when the multipliers change or a base multiplier is deactivated or deleted
+        cached_multiplier_sum: Option<u128>,
         unclaimed_payments: Balance,
         last_updated_period_block: BlockNumber,
+        // Periodicity used instead of the contract one for this beneficiary
+        periodicity_override: Option<u32>,
+        // A frozen beneficiary keeps accruing but can not claim
+        frozen: bool,
+        // Block in which the beneficiary was added, never updated
+        added_at_block: BlockNumber,
+        // Contract period in which the last claim of the beneficiary was counted in claims_in_period
+        // Kept apart from last_updated_period_block, which follows the periodicity override
+        last_claim_period_block: BlockNumber,
     }
 
     /// Initial beneficiary structure containing the account id and the multipliers
@@ -206,6 +324,14 @@ mod open_payroll {
         multipliers: Vec<(MultiplierId, Multiplier)>,
     }
 
+    /// Initial beneficiary structure containing the account id and the multipliers by base multiplier name
+    #[derive(scale::Encode, scale::Decode, Eq, PartialEq, Debug, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct NamedInitialBeneficiary {
+        account_id: AccountId,
+        multipliers: Vec<(String, Multiplier)>,
+    }
+
     /// Claims in period structure containing the period and the total claims
     #[derive(scale::Encode, scale::Decode, Eq, PartialEq, Debug, Clone)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
@@ -214,6 +340,67 @@ mod open_payroll {
         total_claims: u32,
     }
 
+    /// Everything needed to render a beneficiary: the stored data, the amount that can be claimed now,
+    /// the block of the next payout and whether a claim is currently possible
+    #[derive(scale::Encode, scale::Decode, Eq, PartialEq, Debug, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct BeneficiaryView {
+        beneficiary: Beneficiary,
+        amount_to_claim: Balance,
+        next_payout_block: BlockNumber,
+        can_claim: bool,
+    }
+
+    /// What a beneficiary without multipliers is paid each period
+    #[derive(scale::Encode, scale::Decode, Eq, PartialEq, Debug, Clone, Copy)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    #[allow(clippy::enum_variant_names)]
+    pub enum EmptyPolicy {
+        /// The base payment divided by the multiplier base
+        PayFraction,
+        /// Nothing
+        PayNothing,
+        /// The whole base payment
+        PayFullBase,
+    }
+
+    /// Counters of the contract for indexers
+    #[derive(scale::Encode, scale::Decode, Eq, PartialEq, Debug, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Stats {
+        total_paid: Balance,
+        beneficiary_count: u32,
+        multiplier_count: u32,
+        next_multiplier_id: MultiplierId,
+        claims_this_period: u32,
+    }
+
+    /// Role of an account in the contract
+    #[derive(scale::Encode, scale::Decode, Eq, PartialEq, Debug, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Role {
+        /// The owner of the contract
+        Owner,
+        /// A beneficiary of the contract
+        Beneficiary,
+        /// The account has no role in the contract
+        None,
+    }
+
+    /// Status of a base multiplier
+    #[derive(scale::Encode, scale::Decode, Eq, PartialEq, Debug, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum MultiplierStatus {
+        /// The multiplier is active
+        Active,
+        /// The multiplier is deactivated, valid until the given block
+        Deactivated(BlockNumber),
+        /// The multiplier existed and was deleted
+        Deleted,
+        /// The multiplier id was never used
+        NotFound,
+    }
+
     /// OpenPayroll contract structure containing the owner, the beneficiaries, the multipliers, the base payment, the periodicity,
     /// the initial block, the last updated block, the claims in period, the paused state, and the base multipliers
     /// The presence of redundant information between the 'AccountsIds' in 'beneficiaries' and 'beneficiaries_accounts' is intentional.
@@ -228,6 +415,8 @@ mod open_payroll {
     pub struct OpenPayroll {
         /// The account to be transfered to, until the new owner accept it
         proposed_owner: Option<AccountId>,
+        /// The last block in which the proposed owner can accept the ownership, 0 for no expiry
+        proposed_owner_accept_by: BlockNumber,
         /// The accountId of the creator of the contract, who has 'priviliged' access to do administrative tasks
         owner: AccountId,
         /// Mapping from the accountId to the beneficiary information
@@ -238,10 +427,43 @@ mod open_payroll {
         periodicity: u32,
         /// The amount of each base payment
         base_payment: Balance,
+        /// The base payments with the period block from which they are in effect, oldest first
+        /// Pruned to the base payments that can still be accrued by some beneficiary
+        base_payment_history: Vec<(BlockNumber, Balance)>,
         /// The initial block number
         initial_block: u32,
         /// The block number when the contract was paused
         paused_block_at: Option<u32>,
+        /// The reason given by the owner when pausing the contract
+        pause_reason: Option<String>,
+        /// If set, every mutating message except unfreeze_all is blocked
+        contract_frozen: bool,
+        /// The block number when the contract entered the closing state, no payments accrue after it
+        closed_at_block: Option<BlockNumber>,
+        /// Whether the contract was deployed funded for at least one period
+        pre_funded: bool,
+        /// If set, multiplier values can not be above this ratio times the multiplier base
+        max_multiplier_ratio: Option<u128>,
+        /// What the beneficiaries without multipliers are paid
+        empty_multiplier_policy: EmptyPolicy,
+        /// If set, the last active multiplier can not be deactivated
+        require_min_one_multiplier: bool,
+        /// If set, the maximum number of periods that accrue without being claimed
+        max_unclaimed_periods: Option<u32>,
+        /// If set, beneficiaries can only claim a non zero amount once per period
+        one_claim_per_period: bool,
+        /// If set, the number of blocks after the start of a period in which non zero amounts can be claimed
+        claim_window_blocks: Option<u32>,
+        /// If set, payments are only transferred to the accounts in the payout allowlist
+        payout_allowlist_enabled: bool,
+        /// The accounts allowed to receive payments when the payout allowlist is enabled
+        payout_allowlist: Mapping<AccountId, ()>,
+        /// The total amount paid to the beneficiaries
+        total_paid: Balance,
+        /// The total amount paid up to the end of each period with claims, by period initial block
+        total_paid_at_period: Mapping<BlockNumber, Balance>,
+        /// The delegates approved by each beneficiary to claim on their behalf
+        delegates: Mapping<(AccountId, AccountId), bool>,
         /// The id of the next multiplier to be added
         next_multiplier_id: MultiplierId,
         /// The multipliers to apply to the base payment
@@ -267,12 +489,29 @@ mod open_payroll {
             Self {
                 owner,
                 proposed_owner: None,
+                proposed_owner_accept_by: 0,
                 beneficiaries: Default::default(),
                 beneficiaries_accounts: Default::default(),
                 periodicity,
                 base_payment,
+                base_payment_history: Vec::from([(initial_block, base_payment)]),
                 initial_block,
                 paused_block_at: None,
+                pause_reason: None,
+                contract_frozen: false,
+                closed_at_block: None,
+                pre_funded: false,
+                max_multiplier_ratio: None,
+                empty_multiplier_policy: EmptyPolicy::PayFraction,
+                require_min_one_multiplier: false,
+                max_unclaimed_periods: None,
+                one_claim_per_period: false,
+                claim_window_blocks: None,
+                payout_allowlist_enabled: false,
+                payout_allowlist: Mapping::new(),
+                total_paid: 0,
+                total_paid_at_period: Mapping::new(),
+                delegates: Mapping::new(),
                 next_multiplier_id: 0,
                 base_multipliers,
                 multipliers_list: Default::default(),
@@ -285,14 +524,26 @@ mod open_payroll {
 
         /// Constructor that initializes the owner, the base payment, the periodicity, the initial block, the base multipliers,
         /// and the initial beneficiaries
+        /// If require_funded is set, the transferred value must cover one period of payments for all the initial beneficiaries
+        /// If max_multiplier_ratio is set, multiplier values above max_multiplier_ratio * MULTIPLIER_BASE are rejected
+        /// The empty_multiplier_policy sets what the beneficiaries without multipliers are paid
+        /// If require_min_one_multiplier is set, the last active multiplier can not be deactivated
         #[ink(constructor, payable)]
+        #[allow(clippy::too_many_arguments)]
         pub fn new(
             periodicity: u32,
             base_payment: Balance,
             initial_base_multipliers: Vec<String>,
             initial_beneficiaries: Vec<InitialBeneficiary>,
+            require_funded: bool,
+            max_multiplier_ratio: Option<u128>,
+            empty_multiplier_policy: EmptyPolicy,
+            require_min_one_multiplier: bool,
         ) -> Result<Self, Error> {
             let mut instance = Self::default(periodicity, base_payment);
+            instance.max_multiplier_ratio = max_multiplier_ratio;
+            instance.empty_multiplier_policy = empty_multiplier_policy;
+            instance.require_min_one_multiplier = require_min_one_multiplier;
 
             // 0 payment or 0 periodicity make no sense
             if base_payment == 0 || periodicity == 0 {
@@ -312,13 +563,91 @@ mod open_payroll {
                 return Err(Error::MaxMultipliersExceeded);
             }
 
-            instance._create_base_multipliers(initial_base_multipliers);
+            // Ensure the multiplier names are not too long
+            for name in initial_base_multipliers.iter() {
+                ensure_string_length(name)?;
+            }
+
+            instance._create_base_multipliers(initial_base_multipliers)?;
 
             instance._create_initial_beneficiaries(initial_beneficiaries)?;
 
+            // Ensure the contract is funded for at least one period
+            if require_funded
+                && Self::env().transferred_value() < instance.get_total_debt_for_next_period()
+            {
+                return Err(Error::NotEnoughBalanceInTreasury);
+            }
+
+            // Emit the ContractInitialized event
+            Self::env().emit_event(ContractInitialized {
+                owner: instance.owner,
+                base_payment,
+                periodicity,
+                initial_block: instance.initial_block,
+                multiplier_count: instance.multipliers_list.len() as u32,
+                beneficiary_count: instance.beneficiaries_accounts.len() as u32,
+            });
+
+            Ok(instance)
+        }
+
+        /// Constructor with the same params as new that always requires the transferred value to cover one period
+        /// of payments, the contract is marked as pre-funded
+        #[ink(constructor, payable)]
+        pub fn new_funded(
+            periodicity: u32,
+            base_payment: Balance,
+            initial_base_multipliers: Vec<String>,
+            initial_beneficiaries: Vec<InitialBeneficiary>,
+            max_multiplier_ratio: Option<u128>,
+            empty_multiplier_policy: EmptyPolicy,
+            require_min_one_multiplier: bool,
+        ) -> Result<Self, Error> {
+            let mut instance = Self::new(
+                periodicity,
+                base_payment,
+                initial_base_multipliers,
+                initial_beneficiaries,
+                true,
+                max_multiplier_ratio,
+                empty_multiplier_policy,
+                require_min_one_multiplier,
+            )?;
+            instance.pre_funded = true;
+
             Ok(instance)
         }
 
+        /// Constructor with the same params as new, but the multipliers of the initial beneficiaries are given
+        /// by the name of the base multiplier instead of its id
+        #[ink(constructor, payable)]
+        #[allow(clippy::too_many_arguments)]
+        pub fn new_with_named_multipliers(
+            periodicity: u32,
+            base_payment: Balance,
+            initial_base_multipliers: Vec<String>,
+            initial_beneficiaries: Vec<NamedInitialBeneficiary>,
+            require_funded: bool,
+            max_multiplier_ratio: Option<u128>,
+            empty_multiplier_policy: EmptyPolicy,
+            require_min_one_multiplier: bool,
+        ) -> Result<Self, Error> {
+            let initial_beneficiaries =
+                resolve_multiplier_names(&initial_base_multipliers, initial_beneficiaries)?;
+
+            Self::new(
+                periodicity,
+                base_payment,
+                initial_base_multipliers,
+                initial_beneficiaries,
+                require_funded,
+                max_multiplier_ratio,
+                empty_multiplier_policy,
+                require_min_one_multiplier,
+            )
+        }
+
         fn _create_initial_beneficiaries(
             &mut self,
             initial_beneficiaries: Vec<InitialBeneficiary>,
@@ -331,14 +660,21 @@ mod open_payroll {
 
                 // Ensure for duplicate multipliers
                 ensure_no_duplicate_multipliers(&beneficiary_data.multipliers)?;
+                self.ensure_multiplier_values_in_ratio(&beneficiary_data.multipliers)?;
 
                 let multipliers = vec_to_btreemap(&beneficiary_data.multipliers);
+                let cached_multiplier_sum = self._get_multipliers_cache(&multipliers);
 
                 let beneficiary = Beneficiary {
                     account_id: beneficiary_data.account_id,
                     multipliers,
+                    cached_multiplier_sum,
                     unclaimed_payments: 0,
                     last_updated_period_block: self.initial_block,
+                    periodicity_override: None,
+                    frozen: false,
+                    added_at_block: self.initial_block,
+                    last_claim_period_block: self.initial_block,
                 };
 
                 self.beneficiaries
@@ -350,7 +686,10 @@ mod open_payroll {
             Ok(())
         }
 
-        fn _create_base_multipliers(&mut self, initial_base_multipliers: Vec<String>) {
+        fn _create_base_multipliers(
+            &mut self,
+            initial_base_multipliers: Vec<String>,
+        ) -> Result<(), Error> {
             // Create the base multipliers
             for base_multiplier in initial_base_multipliers.iter() {
                 self.base_multipliers.insert(
@@ -358,8 +697,15 @@ mod open_payroll {
                     &BaseMultiplier::new(base_multiplier.clone()),
                 );
                 self.multipliers_list.push(self.next_multiplier_id);
-                self.next_multiplier_id += 1;
+
+                // Increment the next_multiplier_id checking for overflow
+                self.next_multiplier_id = match self.next_multiplier_id.checked_add(1) {
+                    Some(val) => val,
+                    None => return Err(Error::MultiplierIdOverflow),
+                };
             }
+
+            Ok(())
         }
 
         //----------------------------------------------------------------------------------------
@@ -369,13 +715,22 @@ mod open_payroll {
         /// Claim payment for a single account id
         /// If the amount is 0 no money is transferred. However, the "unclaimed_payments" field is set to the total
         /// value that the beneficiary has yet to claim.
+        /// A zero amount never hits the treasury check, so accrual can be settled even when the treasury is short.
+        /// The optional memo is only emitted in the Claimed event, it is not stored.
+        /// Only the beneficiary, the owner or a delegate approved by the beneficiary can claim, the funds
+        /// always go to the beneficiary.
         #[ink(message)]
         pub fn claim_payment(
             &mut self,
             account_id: AccountId,
             amount: Balance,
+            memo: Option<String>,
         ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             self.ensure_is_not_paused()?;
+            if let Some(memo) = &memo {
+                ensure_string_length(memo)?;
+            }
 
             let beneficiary_res = self.beneficiaries.get(account_id);
 
@@ -384,21 +739,22 @@ mod open_payroll {
                 None => return Err(Error::AccountNotFound),
             };
 
-            let current_block = self.env().block_number();
+            let caller = self.env().caller();
+            if caller != account_id
+                && caller != self.owner
+                && !self.delegates.get((account_id, caller)).unwrap_or(false)
+            {
+                return Err(Error::NotAllowedToClaim);
+            }
 
-            // If there are deactivated multipliers, remove them from the beneficiary
-            beneficiary.multipliers.retain(|&k, _| {
-                let multiplier_block_validity =
-                    self.base_multipliers.get(k).unwrap().valid_until_block;
+            if beneficiary.frozen {
+                return Err(Error::BeneficiaryFrozen);
+            }
 
-                // We keep the multiplier if it is not deactivated
-                // or if it is deactivated but the current block is before the deactivation block
-                multiplier_block_validity.is_none()
-                    || multiplier_block_validity.unwrap() > current_block
-            });
+            let current_block = self.env().block_number();
 
             // gets the total amount that the beneficiary can claim and ensure the amount is not bigger than that
-            let total_payment = self._get_amount_to_claim(account_id, true);
+            let total_payment = self._get_amount_to_claim(account_id);
             if amount > total_payment {
                 return Err(Error::ClaimedAmountIsBiggerThanAvailable);
             }
@@ -409,21 +765,61 @@ mod open_payroll {
                 return Err(Error::NotEnoughBalanceInTreasury);
             }
 
-            let claiming_period_block = self.get_current_period_initial_block();
+            // Ensure the claim does not take the unclaimed payments already committed to other beneficiaries
+            if amount > 0
+                && treasury_balance - amount < self._get_unclaimed_payments_of_others(account_id)
+            {
+                return Err(Error::WouldStrandCommittedPayments);
+            }
+
+            let claiming_period_block = self._get_beneficiary_period_initial_block(&beneficiary);
+
+            if self.one_claim_per_period
+                && amount > 0
+                && beneficiary.last_updated_period_block == claiming_period_block
+            {
+                return Err(Error::AlreadyClaimedThisPeriod);
+            }
+
+            if amount > 0
+                && self.payout_allowlist_enabled
+                && !self.payout_allowlist.contains(account_id)
+            {
+                return Err(Error::RecipientNotAllowed);
+            }
 
-            // If the beneficiary has not claimed anything in the current period
-            if beneficiary.last_updated_period_block != claiming_period_block {
-                self._update_claims_in_period(claiming_period_block);
+            if let Some(claim_window_blocks) = self.claim_window_blocks {
+                if amount > 0 && current_block - claiming_period_block > claim_window_blocks {
+                    return Err(Error::OutsideClaimWindow);
+                }
             }
 
+            self._count_claim_in_period(&mut beneficiary);
+
+            // If there are deactivated multipliers, remove them from the beneficiary
+            // Done once all the checks passed, so the pruning is stored together with the other updates
+            beneficiary.multipliers.retain(|&k, _| {
+                // We keep the multiplier if it is not deactivated
+                // or if it still counts in the period the beneficiary is updated to
+                // A deleted multiplier is expired
+                self.base_multipliers.get(k).map_or(false, |multiplier| {
+                    multiplier
+                        .valid_until_block
+                        .map_or(true, |valid_until_block| {
+                            valid_until_block > claiming_period_block
+                        })
+                })
+            });
+
             // Update the beneficiary
+            let cached_multiplier_sum = self._get_multipliers_cache(&beneficiary.multipliers);
             self.beneficiaries.insert(
                 account_id,
                 &Beneficiary {
-                    account_id,
-                    multipliers: beneficiary.multipliers,
+                    cached_multiplier_sum,
                     unclaimed_payments: total_payment - amount,
                     last_updated_period_block: claiming_period_block,
+                    ..beneficiary
                 },
             );
 
@@ -431,6 +827,7 @@ mod open_payroll {
             if amount > 0 && self.env().transfer(account_id, amount).is_err() {
                 return Err(Error::TransferFailed);
             }
+            self.total_paid = self.total_paid.saturating_add(amount);
 
             // Emit the Claimed event
             self.env().emit_event(Claimed {
@@ -438,15 +835,159 @@ mod open_payroll {
                 amount,
                 total_payment,
                 claiming_period_block,
+                treasury_after: self.env().balance(),
+                memo,
+            });
+
+            Ok(())
+        }
+
+        /// Claim a number of accrued periods for a single account id, adding the unclaimed payments if `include_unclaimed`
+        /// The periods are capped at the accrued ones, the periods left stay claimable as unclaimed payments.
+        /// Returns the claimed amount.
+        #[ink(message)]
+        pub fn claim_periods(
+            &mut self,
+            account_id: AccountId,
+            periods: u32,
+            include_unclaimed: bool,
+        ) -> Result<Balance, Error> {
+            self.ensure_contract_not_frozen()?;
+            if periods == 0 {
+                return Err(Error::InvalidParams);
+            }
+            let beneficiary = self
+                .beneficiaries
+                .get(account_id)
+                .ok_or(Error::AccountNotFound)?;
+
+            // The amount accrued up to the end of the given periods, or up to now if it is earlier
+            let periodicity = self._get_beneficiary_periodicity(&beneficiary);
+            let block = beneficiary
+                .last_updated_period_block
+                .saturating_add(periods.saturating_mul(periodicity))
+                .min(self.env().block_number());
+            let mut amount = self._get_amount_to_claim_in_block(account_id, block);
+            if !include_unclaimed {
+                amount = amount.saturating_sub(beneficiary.unclaimed_payments);
+            }
+
+            self.claim_payment(account_id, amount, None)?;
+
+            Ok(amount)
+        }
+
+        /// Claim everything the caller can claim, returns the claimed amount
+        #[ink(message)]
+        pub fn claim_all(&mut self) -> Result<Balance, Error> {
+            self.ensure_contract_not_frozen()?;
+            let account_id = self.env().caller();
+            if !self.beneficiaries.contains(account_id) {
+                return Err(Error::AccountNotFound);
+            }
+
+            let amount = self._get_amount_to_claim(account_id);
+            self.claim_payment(account_id, amount, None)?;
+
+            Ok(amount)
+        }
+
+        /// Claim as much as the treasury can pay now for a single account id
+        /// The amount is capped at the treasury left after the unclaimed payments of the other beneficiaries,
+        /// the rest stays claimable as unclaimed payments. Returns the claimed amount.
+        #[ink(message)]
+        pub fn claim_max(&mut self, account_id: AccountId) -> Result<Balance, Error> {
+            self.ensure_contract_not_frozen()?;
+            if !self.beneficiaries.contains(account_id) {
+                return Err(Error::AccountNotFound);
+            }
+
+            let available = self
+                .env()
+                .balance()
+                .saturating_sub(self._get_unclaimed_payments_of_others(account_id));
+            let amount = self._get_amount_to_claim(account_id).min(available);
+
+            self.claim_payment(account_id, amount, None)?;
+
+            Ok(amount)
+        }
+
+        /// Approve or revoke a delegate to claim the payments of the caller on their behalf
+        /// Only callable by a beneficiary
+        #[ink(message)]
+        pub fn approve_delegate(
+            &mut self,
+            delegate: AccountId,
+            approved: bool,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            let beneficiary = self.env().caller();
+            if !self.beneficiaries.contains(beneficiary) {
+                return Err(Error::AccountNotFound);
+            }
+
+            if approved {
+                self.delegates.insert((beneficiary, delegate), &true);
+            } else {
+                self.delegates.remove((beneficiary, delegate));
+            }
+
+            // Emit the DelegateApprovalChanged event
+            self.env().emit_event(DelegateApprovalChanged {
+                beneficiary,
+                delegate,
+                approved,
             });
 
             Ok(())
         }
 
+        /// Settle the accrued payments of a beneficiary
+        /// Moves everything accrued so far into "unclaimed_payments" and sets "last_updated_period_block"
+        /// to the current period without transferring any funds. Returns the new unclaimed amount.
+        /// The settlement counts as the claim of the beneficiary in the current period: the accrued amount is
+        /// fixed, so a later change of the base payment or the periodicity can not alter it. The owner can
+        /// therefore settle everyone to pass the all claimed in period check.
+        #[ink(message)]
+        pub fn settle_accrual(&mut self, account_id: AccountId) -> Result<Balance, Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+
+            // Ensure that the beneficiary exists
+            if !self.beneficiaries.contains(account_id) {
+                return Err(Error::AccountNotFound);
+            }
+
+            Ok(self._settle_accrual(account_id))
+        }
+
+        /// Settle the accrued payments of every beneficiary
+        /// Useful before updating the base payment or the periodicity, as every settled beneficiary
+        /// counts as claimed in the current period
+        #[ink(message)]
+        pub fn settle_all_accrual(&mut self) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+
+            for account_id in self.beneficiaries_accounts.clone().iter() {
+                self._settle_accrual(*account_id);
+            }
+
+            Ok(())
+        }
+
         /// Deactivate a multiplier
         /// It can be deleted one period after deactivation if every beneficiary has claimed the payment
+        /// If immediate is set, it stops counting from the start of the current period, so the period in progress
+        /// is paid without it. Otherwise it stops counting at the next period.
         #[ink(message)]
-        pub fn deactivate_multiplier(&mut self, multiplier_id: MultiplierId) -> Result<(), Error> {
+        pub fn deactivate_multiplier(
+            &mut self,
+            multiplier_id: MultiplierId,
+            immediate: bool,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             // Fetch the multiplier
             let mut multiplier = self
                 .base_multipliers
@@ -456,14 +997,22 @@ mod open_payroll {
             if multiplier.valid_until_block.is_some() {
                 return Err(Error::MultiplierAlreadyDeactivated);
             }
+            self.ensure_active_multiplier_left(multiplier_id)?;
 
-            // Calculates deactivation on next period
-            let valid_until_block = self.get_current_period_initial_block() + self.periodicity;
+            // Calculates deactivation on the current or the next period
+            let valid_until_block = if immediate {
+                self.get_current_period_initial_block()
+            } else {
+                self.get_current_period_initial_block() + self.periodicity
+            };
 
             // Set that value in the multiplier
             multiplier.valid_until_block = Some(valid_until_block);
             self.base_multipliers.insert(multiplier_id, &multiplier);
 
+            // The deactivated multiplier no longer counts in the cached sums
+            self._refresh_multipliers_cache();
+
             // Emit the MultiplierDeactivated event
             self.env().emit_event(MultiplierDeactivated {
                 multiplier_id,
@@ -479,6 +1028,7 @@ mod open_payroll {
             &mut self,
             multiplier_id: MultiplierId,
         ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             let current_block = self.env().block_number();
             let multiplier = self
                 .base_multipliers
@@ -504,6 +1054,9 @@ mod open_payroll {
             // Remove multiplier from base_multipliers
             self.base_multipliers.remove(multiplier_id);
 
+            // The deleted multiplier no longer counts in the cached sums
+            self._refresh_multipliers_cache();
+
             // Emit the MultiplierDeleted event
             self.env().emit_event(MultiplierDeleted {
                 multiplier_id,
@@ -514,11 +1067,23 @@ mod open_payroll {
         }
 
         /// Change ownership of the contract
-        /// This is proposing a new owner that has to accept the ownership
+        /// This is proposing a new owner that has to accept the ownership up to accept_by_block, 0 for no expiry
         #[ink(message)]
-        pub fn propose_transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Error> {
+        pub fn propose_transfer_ownership(
+            &mut self,
+            new_owner: AccountId,
+            accept_by_block: BlockNumber,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             self.ensure_owner()?;
+
+            // The zero address can not accept the ownership
+            if is_zero_address(&new_owner) {
+                return Err(Error::InvalidParams);
+            }
+
             self.proposed_owner = Some(new_owner);
+            self.proposed_owner_accept_by = accept_by_block;
 
             // Emit the OwnershipTransferred event
             self.env().emit_event(OwnershipProposed {
@@ -534,8 +1099,20 @@ mod open_payroll {
         /// by the new owner. This prevents accidental ownership transfers.
         #[ink(message)]
         pub fn accept_ownership(&mut self) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            // The zero address can never become the owner
+            if is_zero_address(&self.env().caller()) {
+                return Err(Error::InvalidParams);
+            }
+
             let old_owner = self.owner;
             if self.proposed_owner == Some(self.env().caller()) {
+                if self.proposed_owner_accept_by != 0
+                    && self.env().block_number() > self.proposed_owner_accept_by
+                {
+                    return Err(Error::ProposalExpired);
+                }
+
                 self.owner = self.proposed_owner.unwrap();
                 self.proposed_owner = None;
 
@@ -557,11 +1134,13 @@ mod open_payroll {
             account_id: AccountId,
             multipliers: Vec<(MultiplierId, Multiplier)>,
         ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             // Calls the function to do the checking
             self.ensure_beneficiary_to_add(account_id, &multipliers)?;
 
             let multipliers_vec = multipliers.clone();
             let multipliers = vec_to_btreemap(&multipliers);
+            let cached_multiplier_sum = self._get_multipliers_cache(&multipliers);
 
             // insert the new beneficiary
             self.beneficiaries.insert(
@@ -569,8 +1148,13 @@ mod open_payroll {
                 &Beneficiary {
                     account_id,
                     multipliers,
+                    cached_multiplier_sum,
                     unclaimed_payments: 0,
                     last_updated_period_block: self.get_current_period_initial_block(),
+                    periodicity_override: None,
+                    frozen: false,
+                    added_at_block: self.env().block_number(),
+                    last_claim_period_block: self.get_current_period_initial_block(),
                 },
             );
 
@@ -593,53 +1177,84 @@ mod open_payroll {
             account_id: AccountId,
             multipliers: Vec<(MultiplierId, Multiplier)>,
         ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             self.ensure_owner()?;
 
-            // Ensure that the beneficiary exists
-            if !self.beneficiaries.contains(account_id) {
-                return Err(Error::AccountNotFound);
-            }
+            self.ensure_beneficiary_to_update(account_id, &multipliers)?;
+            self._update_beneficiary(account_id, multipliers);
 
-            // Check that the multipliers are valid
-            self.ensure_multipliers_are_valid(&multipliers)?;
-            ensure_no_duplicate_multipliers(&multipliers)?;
-
-            let multipliers_vec = multipliers.clone();
-            let multipliers = vec_to_btreemap(&multipliers);
+            Ok(())
+        }
 
-            // calculate the amount to claim to be transferred to the uncleared payments
-            let unclaimed_payments = self._get_amount_to_claim(account_id, false);
+        /// Update several existing beneficiaries
+        /// Every update is checked before any beneficiary is changed
+        #[ink(message)]
+        pub fn update_beneficiaries(
+            &mut self,
+            updates: Vec<(AccountId, Vec<(MultiplierId, Multiplier)>)>,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
 
-            // update de beneficiary with new multipliers and new unclaimed payments
-            self.beneficiaries.insert(
-                account_id,
-                &Beneficiary {
-                    account_id,
-                    multipliers,
-                    unclaimed_payments,
-                    last_updated_period_block: self.get_current_period_initial_block(),
-                },
-            );
+            ensure_no_duplicate_beneficiaries(
+                &updates.iter().map(|(account_id, _)| *account_id).collect(),
+            )?;
+            for (account_id, multipliers) in updates.iter() {
+                self.ensure_beneficiary_to_update(*account_id, multipliers)?;
+            }
 
-            // Emit the BeneficiaryUpdated event
-            self.env().emit_event(BeneficiaryUpdated {
-                account_id,
-                multipliers_vec,
-            });
+            for (account_id, multipliers) in updates {
+                self._update_beneficiary(account_id, multipliers);
+            }
 
             Ok(())
         }
 
-        /// Remove a beneficiary
+        /// Add a bonus to the unclaimed payments of several beneficiaries
+        /// Every grant is checked before any beneficiary is changed
         #[ink(message)]
-        pub fn remove_beneficiary(&mut self, account_id: AccountId) -> Result<(), Error> {
+        pub fn grant_bonuses(&mut self, grants: Vec<(AccountId, Balance)>) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             self.ensure_owner()?;
-            if !self.beneficiaries.contains(account_id) {
-                return Err(Error::AccountNotFound);
-            }
-            self.beneficiaries.remove(account_id);
 
-            // Remove the beneficiary from the list of beneficiaries
+            ensure_no_duplicate_beneficiaries(
+                &grants.iter().map(|(account_id, _)| *account_id).collect(),
+            )?;
+
+            let mut updated = Vec::new();
+            for (account_id, amount) in grants.iter() {
+                let mut beneficiary = self
+                    .beneficiaries
+                    .get(account_id)
+                    .ok_or(Error::AccountNotFound)?;
+                beneficiary.unclaimed_payments = beneficiary
+                    .unclaimed_payments
+                    .checked_add(*amount)
+                    .ok_or(Error::AmountOverflow)?;
+                updated.push(beneficiary);
+            }
+
+            for (beneficiary, (account_id, amount)) in updated.iter().zip(grants) {
+                self.beneficiaries.insert(account_id, beneficiary);
+
+                // Emit the BonusGranted event
+                self.env().emit_event(BonusGranted { account_id, amount });
+            }
+
+            Ok(())
+        }
+
+        /// Remove a beneficiary
+        #[ink(message)]
+        pub fn remove_beneficiary(&mut self, account_id: AccountId) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+            if !self.beneficiaries.contains(account_id) {
+                return Err(Error::AccountNotFound);
+            }
+            self.beneficiaries.remove(account_id);
+
+            // Remove the beneficiary from the list of beneficiaries
             self.beneficiaries_accounts.retain(|x| *x != account_id);
 
             // Emit the BeneficiaryRemoved event
@@ -648,10 +1263,210 @@ mod open_payroll {
             Ok(())
         }
 
+        /// Remove the multipliers of a beneficiary that no longer exist in the base multipliers
+        #[ink(message)]
+        pub fn prune_beneficiary_multipliers(
+            &mut self,
+            account_id: AccountId,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+
+            let mut beneficiary = self
+                .beneficiaries
+                .get(account_id)
+                .ok_or(Error::AccountNotFound)?;
+
+            // Keep only the multipliers that are still present
+            let mut removed_ids = Vec::new();
+            beneficiary.multipliers.retain(|k, _| {
+                let keep = self.base_multipliers.contains(k);
+                if !keep {
+                    removed_ids.push(*k);
+                }
+                keep
+            });
+            beneficiary.cached_multiplier_sum =
+                self._get_multipliers_cache(&beneficiary.multipliers);
+
+            self.beneficiaries.insert(account_id, &beneficiary);
+
+            self.env().emit_event(BeneficiaryPruned {
+                account_id,
+                removed_ids,
+            });
+
+            Ok(())
+        }
+
+        /// Set a periodicity for a beneficiary to be used instead of the contract one, None to remove it
+        /// The accrued payments are settled first, so the new periodicity applies from the current period,
+        /// or from the next one if the current one starts before the settled block
+        #[ink(message)]
+        pub fn set_beneficiary_periodicity(
+            &mut self,
+            account_id: AccountId,
+            periodicity: Option<u32>,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+            if periodicity == Some(0) {
+                return Err(Error::InvalidParams);
+            }
+
+            // Ensure that the beneficiary exists
+            if !self.beneficiaries.contains(account_id) {
+                return Err(Error::AccountNotFound);
+            }
+
+            self._settle_accrual(account_id);
+
+            let mut beneficiary = self.beneficiaries.get(account_id).unwrap();
+            beneficiary.periodicity_override = periodicity;
+            beneficiary.last_updated_period_block =
+                self._get_beneficiary_accrual_start_block(&beneficiary);
+            self.beneficiaries.insert(account_id, &beneficiary);
+
+            Ok(())
+        }
+
+        /// Freeze or unfreeze the funds of a beneficiary, a frozen beneficiary keeps accruing but can not claim
+        #[ink(message)]
+        pub fn set_beneficiary_frozen(
+            &mut self,
+            account_id: AccountId,
+            frozen: bool,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+
+            let mut beneficiary = self
+                .beneficiaries
+                .get(account_id)
+                .ok_or(Error::AccountNotFound)?;
+            beneficiary.frozen = frozen;
+            self.beneficiaries.insert(account_id, &beneficiary);
+
+            // Emit the BeneficiaryFreezeChanged event
+            self.env()
+                .emit_event(BeneficiaryFreezeChanged { account_id, frozen });
+
+            Ok(())
+        }
+
+        /// Set the block from which the payments of a beneficiary accrue, without paying or touching the unclaimed payments
+        /// The block must be the start of a period of the beneficiary and can not be in the future, nor before
+        /// the oldest base payment kept in the history
+        #[ink(message)]
+        pub fn reset_accrual(
+            &mut self,
+            account_id: AccountId,
+            to_period_block: BlockNumber,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+
+            let mut beneficiary = self
+                .beneficiaries
+                .get(account_id)
+                .ok_or(Error::AccountNotFound)?;
+
+            let periodicity = self._get_beneficiary_periodicity(&beneficiary);
+            if to_period_block < self.initial_block
+                || to_period_block > self.env().block_number()
+                || (to_period_block - self.initial_block) % periodicity != 0
+                || self
+                    .base_payment_history
+                    .first()
+                    .map_or(false, |(block, _)| to_period_block < *block)
+            {
+                return Err(Error::InvalidParams);
+            }
+
+            beneficiary.last_updated_period_block = to_period_block;
+            self.beneficiaries.insert(account_id, &beneficiary);
+
+            Ok(())
+        }
+
+        /// Set the maximum number of periods that accrue without being claimed, None for no limit
+        /// Periods beyond the limit do not accrue, but the payments already settled in unclaimed_payments are kept.
+        /// The limit also applies to the periods accrued before setting it that are not settled yet.
+        #[ink(message)]
+        pub fn set_max_unclaimed_periods(
+            &mut self,
+            max_unclaimed_periods: Option<u32>,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+            if max_unclaimed_periods == Some(0) {
+                return Err(Error::InvalidParams);
+            }
+
+            self.max_unclaimed_periods = max_unclaimed_periods;
+
+            Ok(())
+        }
+
+        /// Allow only one claim of a non zero amount per beneficiary and period
+        /// Claims of 0 are still allowed to settle the payments, but they count as the claim of the period
+        #[ink(message)]
+        pub fn set_one_claim_per_period(
+            &mut self,
+            one_claim_per_period: bool,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+            self.one_claim_per_period = one_claim_per_period;
+            Ok(())
+        }
+
+        /// Set the number of blocks after the start of each period in which non zero amounts can be claimed,
+        /// None to allow claims during the whole period
+        /// Claims of 0 are still allowed outside the window to settle the payments
+        #[ink(message)]
+        pub fn set_claim_window_blocks(
+            &mut self,
+            claim_window_blocks: Option<u32>,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+            self.claim_window_blocks = claim_window_blocks;
+            Ok(())
+        }
+
+        /// Enable or disable the payout allowlist
+        /// While enabled, non zero amounts are only transferred to the allowlisted accounts
+        #[ink(message)]
+        pub fn set_payout_allowlist_enabled(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+            self.payout_allowlist_enabled = enabled;
+            Ok(())
+        }
+
+        /// Add or remove an account from the payout allowlist
+        #[ink(message)]
+        pub fn set_payout_allowed(
+            &mut self,
+            account_id: AccountId,
+            allowed: bool,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+            if allowed {
+                self.payout_allowlist.insert(account_id, &());
+            } else {
+                self.payout_allowlist.remove(account_id);
+            }
+            Ok(())
+        }
+
         /// Update the base_payment
-        /// It makes sense once all the beneficiaries have claimed their payments
+        /// It makes sense once all the beneficiaries have claimed their payments, or have been settled
         #[ink(message)]
         pub fn update_base_payment(&mut self, base_payment: Balance) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             self.ensure_owner()?;
             if base_payment == 0 {
                 return Err(Error::InvalidParams);
@@ -661,6 +1476,36 @@ mod open_payroll {
             self.ensure_all_claimed_in_period()?;
             self.base_payment = base_payment;
 
+            // The new base payment is in effect from the current period
+            let current_period_block = self.get_current_period_initial_block();
+            match self.base_payment_history.last_mut() {
+                Some((block, last_base_payment)) if *block == current_period_block => {
+                    *last_base_payment = base_payment
+                }
+                _ => self
+                    .base_payment_history
+                    .push((current_period_block, base_payment)),
+            }
+
+            // Drop the base payments no beneficiary can accrue anymore, keeping the one in effect at the oldest cursor
+            let oldest_cursor = self
+                .beneficiaries_accounts
+                .iter()
+                .map(|account_id| {
+                    self.beneficiaries
+                        .get(account_id)
+                        .unwrap()
+                        .last_updated_period_block
+                })
+                .min()
+                .unwrap_or(current_period_block);
+            let in_effect = self
+                .base_payment_history
+                .iter()
+                .rposition(|(block, _)| *block <= oldest_cursor)
+                .unwrap_or(0);
+            self.base_payment_history.drain(..in_effect);
+
             Ok(())
         }
 
@@ -668,7 +1513,9 @@ mod open_payroll {
         /// It's not checking for duplicates because it's just a string
         #[ink(message)]
         pub fn add_base_multiplier(&mut self, name: String) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             self.ensure_owner()?;
+            ensure_string_length(&name)?;
 
             // Ensure that the number of multipliers does not exceed the maximum
             if self.multipliers_list.len() + 1 > MAX_MULTIPLIERS {
@@ -699,8 +1546,19 @@ mod open_payroll {
 
         /// Update the periodicity of the payments
         /// All payments must be claimed before updating the periodicity
+        /// The accrued payments are settled and every beneficiary starts accruing from its period start
+        /// under the new periodicity, or from the next one if it starts before the settled block, so no
+        /// accrual is paid twice across the change
+        /// If re_anchor is set, the initial block is moved to the start of the current period, so the periods
+        /// of the new periodicity start from there instead of from the original initial block. Payments accrued
+        /// before it are kept as unclaimed payments.
         #[ink(message)]
-        pub fn update_periodicity(&mut self, periodicity: u32) -> Result<(), Error> {
+        pub fn update_periodicity(
+            &mut self,
+            periodicity: u32,
+            re_anchor: bool,
+        ) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             self.ensure_owner()?;
             if periodicity == 0 {
                 return Err(Error::InvalidParams);
@@ -709,24 +1567,45 @@ mod open_payroll {
             // Ensure if all payments are up to date
             // self.ensure_all_payments_uptodate()?;
             self.ensure_all_claimed_in_period()?;
+
+            let accounts = self.beneficiaries_accounts.clone();
+            for account_id in accounts.iter() {
+                self._settle_accrual(*account_id);
+            }
+
+            if re_anchor {
+                self.initial_block = self.get_current_period_initial_block();
+            }
             self.periodicity = periodicity;
 
+            for account_id in accounts.iter() {
+                let mut beneficiary = self.beneficiaries.get(account_id).unwrap();
+                beneficiary.last_updated_period_block =
+                    self._get_beneficiary_accrual_start_block(&beneficiary);
+                self.beneficiaries.insert(account_id, &beneficiary);
+            }
+
             // Emit the PeriodicityUpdated event
             self.env().emit_event(PeriodicityUpdated { periodicity });
 
             Ok(())
         }
 
-        /// Pause the contract
+        /// Pause the contract with an optional reason
         /// Pausing will only avoid to call the claim function
         #[ink(message)]
-        pub fn pause(&mut self) -> Result<(), Error> {
+        pub fn pause(&mut self, reason: Option<String>) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             self.ensure_owner()?;
             if self.is_paused() {
                 return Ok(());
             }
+            if let Some(reason) = &reason {
+                ensure_string_length(reason)?;
+            }
             self.paused_block_at = Some(self.env().block_number());
-            self.env().emit_event(Paused {});
+            self.pause_reason = reason.clone();
+            self.env().emit_event(Paused { reason });
             Ok(())
         }
 
@@ -734,15 +1613,153 @@ mod open_payroll {
         /// Resuming will allow to call the claim function
         #[ink(message)]
         pub fn resume(&mut self) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
             self.ensure_owner()?;
             if !self.is_paused() {
                 return Ok(());
             }
             self.paused_block_at = None;
+            self.pause_reason = None;
             self.env().emit_event(Resumed {});
             Ok(())
         }
 
+        /// Freeze the whole contract for incident response
+        /// Unlike pausing, every mutating message is blocked until unfreeze_all is called, reads keep working
+        #[ink(message)]
+        pub fn freeze_all(&mut self) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.ensure_contract_not_frozen()?;
+            self.contract_frozen = true;
+            self.env()
+                .emit_event(ContractFreezeChanged { frozen: true });
+            Ok(())
+        }
+
+        /// Unfreeze the whole contract
+        #[ink(message)]
+        pub fn unfreeze_all(&mut self) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if !self.contract_frozen {
+                return Ok(());
+            }
+            self.contract_frozen = false;
+            self.env()
+                .emit_event(ContractFreezeChanged { frozen: false });
+            Ok(())
+        }
+
+        /// Transfer the whole treasury to a new payroll contract
+        /// The contract enters the closing state, so no new payments accrue
+        #[ink(message)]
+        pub fn rollover_to(&mut self, new_contract: AccountId) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+            if new_contract == self.env().account_id() {
+                return Err(Error::InvalidParams);
+            }
+
+            self._enter_closing();
+
+            let amount = self.env().balance();
+            if self.env().transfer(new_contract, amount).is_err() {
+                return Err(Error::TransferFailed);
+            }
+
+            // Emit the RolledOver event
+            self.env().emit_event(RolledOver {
+                to: new_contract,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Pay every beneficiary that is not frozen and is allowed to receive payments all their claimable amount,
+        /// only while closing
+        /// Nothing is paid if the treasury can not cover all the payments, returns the number of beneficiaries paid
+        #[ink(message)]
+        pub fn final_distribute(&mut self) -> Result<u32, Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+            if !self.is_closing() {
+                return Err(Error::ContractNotClosing);
+            }
+
+            let payees: Vec<AccountId> = self
+                .beneficiaries_accounts
+                .iter()
+                .filter(|account_id| {
+                    !self.beneficiaries.get(*account_id).unwrap().frozen
+                        && self.is_payout_allowed(**account_id)
+                })
+                .cloned()
+                .collect();
+
+            // Ensure the treasury covers every payment before paying anyone
+            let total: Balance = payees
+                .iter()
+                .map(|account_id| self._get_amount_to_claim(*account_id))
+                .sum();
+            let treasury_balance = self.env().balance();
+            if total > treasury_balance {
+                return Err(Error::NotEnoughBalanceInTreasury);
+            }
+
+            let mut paid = 0;
+            for account_id in payees {
+                let amount = self._settle_accrual(account_id);
+                if amount == 0 {
+                    continue;
+                }
+
+                let mut beneficiary = self.beneficiaries.get(account_id).unwrap();
+                beneficiary.unclaimed_payments = 0;
+                self.beneficiaries.insert(account_id, &beneficiary);
+
+                if self.env().transfer(account_id, amount).is_err() {
+                    return Err(Error::TransferFailed);
+                }
+                self.total_paid = self.total_paid.saturating_add(amount);
+
+                self.env().emit_event(Claimed {
+                    account_id,
+                    amount,
+                    total_payment: amount,
+                    claiming_period_block: beneficiary.last_updated_period_block,
+                    treasury_after: self.env().balance(),
+                    memo: None,
+                });
+                paid += 1;
+            }
+
+            Ok(paid)
+        }
+
+        /// Withdraw the whole treasury to the owner as a last resort
+        /// It works even if the contract is paused, and leaves the contract paused and in the closing state
+        #[ink(message)]
+        pub fn emergency_withdraw(&mut self) -> Result<(), Error> {
+            self.ensure_contract_not_frozen()?;
+            self.ensure_owner()?;
+
+            self._enter_closing();
+            if !self.is_paused() {
+                self.paused_block_at = Some(self.env().block_number());
+                self.env().emit_event(Paused { reason: None });
+            }
+
+            let amount = self.env().balance();
+            if self.env().transfer(self.owner, amount).is_err() {
+                return Err(Error::TransferFailed);
+            }
+
+            // Emit the EmergencyWithdraw event
+            self.env().emit_event(EmergencyWithdraw { amount });
+
+            Ok(())
+        }
+
         //----------------------------------------------------------------------------------------
         // Read messages
         //----------------------------------------------------------------------------------------
@@ -752,11 +1769,10 @@ mod open_payroll {
         /// The view function should just return a bool, and the ensure function should return an error
         #[ink(message)]
         pub fn ensure_all_payments_uptodate(&self) -> Result<(), Error> {
-            let claimed_period_block = self.get_current_period_initial_block();
-
             // iterates over each account_id
             for account_id in self.beneficiaries_accounts.iter() {
                 let beneficiary = self.beneficiaries.get(account_id).unwrap();
+                let claimed_period_block = self._get_beneficiary_period_initial_block(&beneficiary);
 
                 if claimed_period_block > beneficiary.last_updated_period_block {
                     return Err(Error::PaymentsNotUpToDate);
@@ -766,12 +1782,101 @@ mod open_payroll {
             Ok(())
         }
 
+        /// Get the beneficiaries that make ensure_all_payments_uptodate fail,
+        /// the ones that have not been updated since the start of their current period
+        /// Read Only function
+        #[ink(message)]
+        pub fn get_overdue_beneficiaries(&self) -> Vec<AccountId> {
+            self.beneficiaries_accounts
+                .iter()
+                .filter(|account_id| {
+                    let beneficiary = self.beneficiaries.get(*account_id).unwrap();
+                    self._get_beneficiary_period_initial_block(&beneficiary)
+                        > beneficiary.last_updated_period_block
+                })
+                .cloned()
+                .collect()
+        }
+
+        /// Get the beneficiaries whose multipliers do not match the active base multipliers
+        /// Multipliers that are deactivated but not deleted yet are ignored, as beneficiaries keep them until they claim
+        /// Read Only function
+        #[ink(message)]
+        pub fn find_inconsistent_beneficiaries(&self) -> Vec<AccountId> {
+            let active_ids: Vec<MultiplierId> = self
+                .multipliers_list
+                .iter()
+                .filter(|id| {
+                    self.base_multipliers
+                        .get(*id)
+                        .map_or(false, |m| m.valid_until_block.is_none())
+                })
+                .cloned()
+                .collect();
+
+            self.beneficiaries_accounts
+                .iter()
+                .filter(|account_id| {
+                    let beneficiary = self.beneficiaries.get(*account_id).unwrap();
+                    let ids: Vec<MultiplierId> = beneficiary
+                        .multipliers
+                        .keys()
+                        .filter(|id| {
+                            self.base_multipliers
+                                .get(*id)
+                                .map_or(true, |m| m.valid_until_block.is_none())
+                        })
+                        .cloned()
+                        .collect();
+                    ids.len() != active_ids.len() || ids.iter().any(|id| !active_ids.contains(id))
+                })
+                .cloned()
+                .collect()
+        }
+
         /// Reads the paused state from the contract
         #[ink(message)]
         pub fn is_paused(&self) -> bool {
             self.paused_block_at.is_some()
         }
 
+        /// Reads the reason given when the contract was paused
+        #[ink(message)]
+        pub fn get_pause_reason(&self) -> Option<String> {
+            self.pause_reason.clone()
+        }
+
+        /// Check if a delegate is approved to claim on behalf of a beneficiary
+        /// Read Only function
+        #[ink(message)]
+        pub fn is_delegate_approved(&self, beneficiary: AccountId, delegate: AccountId) -> bool {
+            self.delegates.get((beneficiary, delegate)).unwrap_or(false)
+        }
+
+        /// Reads if an account can receive payments, always true while the payout allowlist is disabled
+        #[ink(message)]
+        pub fn is_payout_allowed(&self, account_id: AccountId) -> bool {
+            !self.payout_allowlist_enabled || self.payout_allowlist.contains(account_id)
+        }
+
+        /// Reads if the whole contract is frozen
+        #[ink(message)]
+        pub fn is_contract_frozen(&self) -> bool {
+            self.contract_frozen
+        }
+
+        /// Reads if the contract was deployed with new_funded
+        #[ink(message)]
+        pub fn is_pre_funded(&self) -> bool {
+            self.pre_funded
+        }
+
+        /// Reads the closing state from the contract
+        #[ink(message)]
+        pub fn is_closing(&self) -> bool {
+            self.closed_at_block.is_some()
+        }
+
         /// Get amount in storage without transferring the funds
         /// Read Only function
         #[ink(message)]
@@ -780,10 +1885,63 @@ mod open_payroll {
                 return None;
             }
 
-            let result = self._get_amount_to_claim(account_id, false);
+            let result = self._get_amount_to_claim(account_id);
             Some(result)
         }
 
+        /// Get the payout of one period for a beneficiary, without the accrued periods and the unclaimed payments
+        /// Read Only function
+        #[ink(message)]
+        pub fn get_period_payout(&self, account_id: AccountId) -> Result<Balance, Error> {
+            let beneficiary = self
+                .beneficiaries
+                .get(account_id)
+                .ok_or(Error::AccountNotFound)?;
+
+            Ok(self._get_amount_to_claim_for_one_period(&beneficiary, false))
+        }
+
+        /// Get the beneficiary data together with its claimable amount, next payout block and whether it can claim
+        /// Read Only function
+        #[ink(message)]
+        pub fn get_beneficiary_view(
+            &self,
+            account_id: AccountId,
+        ) -> Result<BeneficiaryView, Error> {
+            let beneficiary = self
+                .beneficiaries
+                .get(account_id)
+                .ok_or(Error::AccountNotFound)?;
+
+            let amount_to_claim = self._get_amount_to_claim(account_id);
+            let next_payout_block = self._get_beneficiary_period_initial_block(&beneficiary)
+                + self._get_beneficiary_periodicity(&beneficiary);
+            let can_claim = !self.is_paused() && !beneficiary.frozen && amount_to_claim > 0;
+
+            Ok(BeneficiaryView {
+                beneficiary,
+                amount_to_claim,
+                next_payout_block,
+                can_claim,
+            })
+        }
+
+        /// Get the amount to claim of several accounts, with an error in the slot of each account that is not found
+        /// Read Only function
+        #[ink(message)]
+        pub fn get_amounts_to_claim(
+            &self,
+            accounts: Vec<AccountId>,
+        ) -> Vec<Result<Balance, Error>> {
+            accounts
+                .iter()
+                .map(|account_id| {
+                    self.get_amount_to_claim(*account_id)
+                        .ok_or(Error::AccountNotFound)
+                })
+                .collect()
+        }
+
         /// Get beneficiary only read
         /// Read Only function
         #[ink(message)]
@@ -791,6 +1949,39 @@ mod open_payroll {
             self.beneficiaries.get(account_id)
         }
 
+        /// Get the multipliers of a beneficiary sorted by multiplier id
+        /// Read Only function
+        #[ink(message)]
+        pub fn get_beneficiary_multipliers(
+            &self,
+            account_id: AccountId,
+        ) -> Result<Vec<(MultiplierId, Multiplier)>, Error> {
+            let beneficiary = self
+                .beneficiaries
+                .get(account_id)
+                .ok_or(Error::AccountNotFound)?;
+
+            // BTreeMap iterates in key order
+            Ok(beneficiary.multipliers.into_iter().collect())
+        }
+
+        /// Get the block from which the payments of a beneficiary are not settled yet
+        /// Read Only function
+        #[ink(message)]
+        pub fn get_last_updated(&self, account_id: AccountId) -> Result<BlockNumber, Error> {
+            self.beneficiaries
+                .get(account_id)
+                .map(|beneficiary| beneficiary.last_updated_period_block)
+                .ok_or(Error::AccountNotFound)
+        }
+
+        /// Check if an account is a beneficiary
+        /// Read Only function
+        #[ink(message)]
+        pub fn is_beneficiary(&self, account_id: AccountId) -> bool {
+            self.beneficiaries.contains(account_id)
+        }
+
         /// Get current block period
         /// Read Only function
         /// The calculation current_block - ((current_block - self.initial_block) % self.periodicity) might be a bit tricky to understand at first glance.
@@ -810,44 +2001,149 @@ mod open_payroll {
             self.get_current_period_initial_block() + self.periodicity
         }
 
-        /// Get all the debts up-to-date
+        /// Get the block in which the next full period of a beneficiary completes, using its own periodicity
+        /// and the block from which it accrues
+        #[ink(message)]
+        pub fn get_beneficiary_next_period(
+            &self,
+            account_id: AccountId,
+        ) -> Result<BlockNumber, Error> {
+            let beneficiary = self
+                .beneficiaries
+                .get(account_id)
+                .ok_or(Error::AccountNotFound)?;
+
+            let periodicity = self._get_beneficiary_periodicity(&beneficiary);
+            let elapsed_blocks = self
+                .env()
+                .block_number()
+                .saturating_sub(beneficiary.last_updated_period_block);
+
+            Ok(beneficiary.last_updated_period_block
+                + (elapsed_blocks / periodicity + 1) * periodicity)
+        }
+
+        /// Get the number of whole periods elapsed since the initial block, 0 if the contract has not started yet
+        #[ink(message)]
+        pub fn periods_elapsed(&self) -> u32 {
+            let current_block = self.env().block_number();
+            current_block.saturating_sub(self.initial_block) / self.periodicity
+        }
+
+        /// Get the sum of the stored multipliers of all the beneficiaries, saturating on overflow
+        /// Read Only function
+        #[ink(message)]
+        pub fn total_multiplier_weight(&self) -> u128 {
+            let mut total: u128 = 0;
+            for account_id in self.beneficiaries_accounts.iter() {
+                let beneficiary = self.beneficiaries.get(account_id).unwrap();
+                for multiplier in beneficiary.multipliers.values() {
+                    total = total.saturating_add(*multiplier);
+                }
+            }
+            total
+        }
+
+        /// Get the index of the period a block belongs to, 0 for blocks before the initial block
+        #[ink(message)]
+        pub fn period_index_at(&self, block: BlockNumber) -> u32 {
+            block.saturating_sub(self.initial_block) / self.periodicity
+        }
+
+        /// Get all the debts up-to-date, saturating at the maximum balance instead of overflowing
         /// Read Only function
         #[ink(message)]
         pub fn get_total_debts(&self) -> Balance {
-            let mut debts = 0;
+            let mut debts: Balance = 0;
             for account_id in self.beneficiaries_accounts.iter() {
                 let beneficiary = self.beneficiaries.get(account_id).unwrap();
-                debts += self._get_amount_to_claim(beneficiary.account_id, false);
+                debts = debts.saturating_add(self._get_amount_to_claim(beneficiary.account_id));
             }
 
             debts
         }
 
-        /// Get all the debts for the next period
+        /// Get all the debts for the next period, saturating at the maximum balance instead of overflowing
         /// Read Only function
         #[ink(message)]
         pub fn get_total_debt_for_next_period(&self) -> Balance {
-            let mut total = 0;
+            let mut total: Balance = 0;
             for account_id in self.beneficiaries_accounts.iter() {
                 let beneficiary = self.beneficiaries.get(account_id).unwrap();
                 let amount = self._get_amount_to_claim_for_one_period(&beneficiary, false);
-                total += amount;
+                total = total.saturating_add(amount);
             }
 
             total
         }
 
-        /// Get all the debts including unclaimed for the next period
+        /// Get what each beneficiary accrues in exactly one upcoming period, without unclaimed payments
+        /// Read Only function
+        #[ink(message)]
+        pub fn preview_next_period(&self) -> Vec<(AccountId, Balance)> {
+            self.beneficiaries_accounts
+                .iter()
+                .map(|account_id| {
+                    let beneficiary = self.beneficiaries.get(account_id).unwrap();
+                    let amount = self._get_amount_to_claim_for_one_period(&beneficiary, false);
+                    (*account_id, amount)
+                })
+                .collect()
+        }
+
+        /// Get what each beneficiary accrues in one period with the current and with a new base payment
+        /// Returns a list of (account, current amount, projected amount)
+        /// Read Only function
+        #[ink(message)]
+        pub fn preview_base_payment_change(
+            &self,
+            new_base_payment: Balance,
+        ) -> Vec<(AccountId, Balance, Balance)> {
+            self.beneficiaries_accounts
+                .iter()
+                .map(|account_id| {
+                    let beneficiary = self.beneficiaries.get(account_id).unwrap();
+                    let current = self._get_amount_to_claim_for_one_period(&beneficiary, false);
+                    let projected = self._get_amount_for_one_period_with_base_payment(
+                        &beneficiary,
+                        false,
+                        new_base_payment,
+                    );
+                    (*account_id, current, projected)
+                })
+                .collect()
+        }
+
+        /// Get all the debts including unclaimed for the next period, saturating at the maximum balance
+        /// instead of overflowing
         /// Read Only function
         #[ink(message)]
         pub fn get_total_debt_with_unclaimed_for_next_period(&self) -> Balance {
             let block_next_period = self.get_next_block_period();
 
-            let mut total = 0;
+            let mut total: Balance = 0;
+            for account_id in self.beneficiaries_accounts.iter() {
+                let amount = self._get_amount_to_claim_in_block(*account_id, block_next_period);
+                total = total.saturating_add(amount);
+            }
+
+            total
+        }
+
+        /// Get all the debts including unclaimed up to a number of periods after the current one, saturating
+        /// at the maximum balance instead of overflowing
+        /// Read Only function
+        #[ink(message)]
+        pub fn projected_liability(&self, periods_ahead: u32) -> Balance {
+            let block = periods_ahead
+                .checked_mul(self.periodicity)
+                .and_then(|blocks| blocks.checked_add(self.get_current_period_initial_block()))
+                .unwrap_or(BlockNumber::MAX);
+
+            let mut total: Balance = 0;
             for account_id in self.beneficiaries_accounts.iter() {
-                let amount =
-                    self._get_amount_to_claim_in_block(*account_id, false, block_next_period);
-                total += amount;
+                let amount = self._get_amount_to_claim_in_block(*account_id, block);
+                total = total.saturating_add(amount);
             }
 
             total
@@ -867,25 +2163,90 @@ mod open_payroll {
             self.env().balance()
         }
 
-        /// Get total balance after paying debts
+        /// Get the account id of the contract
+        /// Read Only function
+        #[ink(message)]
+        pub fn get_contract_account(&self) -> AccountId {
+            self.env().account_id()
+        }
+
+        /// Get total balance after paying debts, 0 if the debts exceed the balance
         /// Read Only function
         #[ink(message)]
         pub fn get_balance_with_debts(&self) -> Balance {
-            self.get_contract_balance() - self.get_total_debts()
+            self.get_contract_balance()
+                .saturating_sub(self.get_total_debts())
+        }
+
+        /// Get the treasury balance over the debts up-to-date in basis points, u32::MAX if there are no debts
+        /// Read Only function
+        #[ink(message)]
+        pub fn solvency_bps(&self) -> u32 {
+            let debts = self.get_total_debts();
+            if debts == 0 {
+                return u32::MAX;
+            }
+
+            let bps = self.get_contract_balance().saturating_mul(10_000) / debts;
+            u32::try_from(bps).unwrap_or(u32::MAX)
+        }
+
+        /// Get the treasury balance, the debts up-to-date and the difference between them
+        /// A positive difference is a surplus and a negative one a shortfall, values beyond i128 are clamped
+        /// Read Only function
+        #[ink(message)]
+        pub fn treasury_reconciliation(&self) -> (Balance, Balance, i128) {
+            let balance = self.get_contract_balance();
+            let debts = self.get_total_debts();
+            let difference = i128::try_from(balance)
+                .unwrap_or(i128::MAX)
+                .saturating_sub(i128::try_from(debts).unwrap_or(i128::MAX));
+
+            (balance, debts, difference)
+        }
+
+        /// Get the highest base payment the treasury can pay for one period with the current multipliers,
+        /// Balance::MAX if the multipliers add up to zero
+        /// Read Only function
+        #[ink(message)]
+        pub fn max_sustainable_base_payment(&self) -> Balance {
+            // The final multipliers, including the empty multiplier policy, are the ones paid
+            let weight = self
+                .beneficiaries_accounts
+                .iter()
+                .map(|account_id| {
+                    let beneficiary = self.beneficiaries.get(account_id).unwrap();
+                    self._get_final_multiplier(&beneficiary, false)
+                })
+                .fold(0u128, |total, multiplier| total.saturating_add(multiplier));
+            if weight == 0 {
+                return Balance::MAX;
+            }
+
+            // Inverse of (M1 + M2) * B / 100
+            self.get_contract_balance().saturating_mul(MULTIPLIER_BASE) / weight
+        }
+
+        /// Get the amount to deposit so the treasury covers all the debts including the next period
+        /// Read Only function
+        #[ink(message)]
+        pub fn required_topup_for_next_period(&self) -> Balance {
+            self.get_total_debt_with_unclaimed_for_next_period()
+                .saturating_sub(self.get_contract_balance())
         }
 
         /// Get list of unclaimed beneficiaries
         /// Read Only function
         #[ink(message)]
         pub fn get_unclaimed_beneficiaries(&self) -> Vec<AccountId> {
-            let claiming_period_block = self.get_current_period_initial_block();
-
             let mut unclaimed_beneficiaries = Vec::new();
             // iterate over all beneficiaries
-            // if last_updated_period_block < claiming_period_block
+            // if last_updated_period_block < claiming_period_block of the beneficiary
             // then add to unclaimed_beneficiaries
             for account_id in self.beneficiaries_accounts.iter() {
                 let beneficiary = self.beneficiaries.get(account_id).unwrap();
+                let claiming_period_block =
+                    self._get_beneficiary_period_initial_block(&beneficiary);
                 if beneficiary.last_updated_period_block < claiming_period_block {
                     unclaimed_beneficiaries.push(beneficiary.account_id);
                 }
@@ -894,14 +2255,57 @@ mod open_payroll {
             unclaimed_beneficiaries
         }
 
+        /// Get the current period block, the claims made in it and the claims required to complete it
+        /// Read Only function
+        #[ink(message)]
+        pub fn claims_status(&self) -> (u32, u32, u32) {
+            let current_period_block = self.get_current_period_initial_block();
+
+            // The stored claims belong to a previous period until someone claims in the current one
+            let total_claims = if self.claims_in_period.period == current_period_block {
+                self.claims_in_period.total_claims
+            } else {
+                0
+            };
+
+            (
+                current_period_block,
+                total_claims,
+                self.beneficiaries_accounts.len() as u32,
+            )
+        }
+
+        /// Check if update_base_payment and update_periodicity would pass the all claimed in period check now
+        /// Settlements by the owner count as claims
+        /// Read Only function
+        #[ink(message)]
+        pub fn can_update_schedule(&self) -> bool {
+            self.ensure_all_claimed_in_period().is_ok()
+        }
+
+        /// Get the beneficiaries with stored unclaimed payments and their amount, without the payments still accruing
+        /// Read Only function
+        #[ink(message)]
+        pub fn get_beneficiaries_with_unclaimed(&self) -> Vec<(AccountId, Balance)> {
+            self.beneficiaries_accounts
+                .iter()
+                .map(|account_id| {
+                    let beneficiary = self.beneficiaries.get(account_id).unwrap();
+                    (*account_id, beneficiary.unclaimed_payments)
+                })
+                .filter(|(_, unclaimed_payments)| *unclaimed_payments > 0)
+                .collect()
+        }
+
         /// Get count of unclaimed beneficiaries
         /// Read Only function
         #[ink(message)]
-        pub fn get_count_of_unclaim_beneficiaries(&self) -> u8 {
-            let claiming_period_block = self.get_current_period_initial_block();
-            let mut total: u8 = 0;
+        pub fn get_count_of_unclaim_beneficiaries(&self) -> u32 {
+            let mut total: u32 = 0;
             for account_id in self.beneficiaries_accounts.iter() {
                 let beneficiary = self.beneficiaries.get(account_id).unwrap();
+                let claiming_period_block =
+                    self._get_beneficiary_period_initial_block(&beneficiary);
                 if beneficiary.last_updated_period_block < claiming_period_block {
                     total += 1;
                 }
@@ -916,12 +2320,27 @@ mod open_payroll {
             self.base_payment
         }
 
-        /// Get the periodicity of the payments
+        /// Get the base payments with the period block from which they are in effect, oldest first
+        /// Only the base payments that can still be accrued by some beneficiary are kept
+        #[ink(message)]
+        pub fn get_base_payment_history(&self) -> Vec<(BlockNumber, Balance)> {
+            self.base_payment_history.clone()
+        }
+
+        /// Get the periodicity of the payments, as the number of blocks in a period
         #[ink(message)]
-        pub fn get_periodicity(&self) -> BlockNumber {
+        pub fn get_periodicity(&self) -> u32 {
             self.periodicity
         }
 
+        /// Suggest a periodicity in blocks for a target duration
+        /// E.g. a week (604_800 seconds) with 6_000 ms blocks is 100_800 blocks
+        /// Read Only function
+        #[ink(message)]
+        pub fn suggest_periodicity(&self, target_seconds: u64, avg_block_time_ms: u64) -> u32 {
+            suggest_periodicity(target_seconds, avg_block_time_ms)
+        }
+
         /// Get the initial block of the contract
         #[ink(message)]
         pub fn get_initial_block(&self) -> BlockNumber {
@@ -940,12 +2359,97 @@ mod open_payroll {
             self.base_multipliers.get(multiplier_id)
         }
 
+        /// Get the status of a base multiplier
+        #[ink(message)]
+        pub fn get_multiplier_status(&self, multiplier_id: MultiplierId) -> MultiplierStatus {
+            match self.base_multipliers.get(multiplier_id) {
+                Some(BaseMultiplier {
+                    valid_until_block: Some(block),
+                    ..
+                }) => MultiplierStatus::Deactivated(block),
+                Some(_) => MultiplierStatus::Active,
+                // Ids are never reused, so a missing id below the next one was deleted
+                None if multiplier_id < self.next_multiplier_id => MultiplierStatus::Deleted,
+                None => MultiplierStatus::NotFound,
+            }
+        }
+
+        /// Get the status of several base multipliers at once
+        #[ink(message)]
+        pub fn get_multiplier_statuses(
+            &self,
+            ids: Vec<MultiplierId>,
+        ) -> Vec<(MultiplierId, MultiplierStatus)> {
+            ids.into_iter()
+                .map(|id| (id, self.get_multiplier_status(id)))
+                .collect()
+        }
+
+        /// Get every base multiplier with its id, both active and deactivated, to replicate the configuration
+        #[ink(message)]
+        pub fn export_multipliers(&self) -> Vec<(MultiplierId, BaseMultiplier)> {
+            self.multipliers_list
+                .iter()
+                .map(|id| (*id, self.base_multipliers.get(id).unwrap()))
+                .collect()
+        }
+
+        /// Get the block after which a deactivated multiplier can be deleted
+        #[ink(message)]
+        pub fn get_deletable_at(&self, multiplier_id: MultiplierId) -> Result<BlockNumber, Error> {
+            self.base_multipliers
+                .get(multiplier_id)
+                .ok_or(Error::MultiplierNotFound)?
+                .valid_until_block
+                .ok_or(Error::MultiplierNotDeactivated)
+        }
+
         /// Get the owner of the contract
         #[ink(message)]
         pub fn get_owner(&self) -> AccountId {
             self.owner
         }
 
+        /// Get the total amount paid up to the end of a period, given its initial block
+        /// Only recorded for the periods with claims, once the next claim happens in a later period
+        #[ink(message)]
+        pub fn get_total_paid_at_period(&self, period_block: BlockNumber) -> Option<Balance> {
+            self.total_paid_at_period.get(period_block)
+        }
+
+        /// Get the counters of the contract in one call
+        #[ink(message)]
+        pub fn get_stats(&self) -> Stats {
+            let (_, claims_this_period, _) = self.claims_status();
+
+            Stats {
+                total_paid: self.total_paid,
+                beneficiary_count: self.beneficiaries_accounts.len() as u32,
+                multiplier_count: self.multipliers_list.len() as u32,
+                next_multiplier_id: self.next_multiplier_id,
+                claims_this_period,
+            }
+        }
+
+        /// Get the role of the caller, the owner role takes precedence over the beneficiary one
+        #[ink(message)]
+        pub fn my_role(&self) -> Role {
+            let caller = self.env().caller();
+            if caller == self.owner {
+                Role::Owner
+            } else if self.beneficiaries.contains(caller) {
+                Role::Beneficiary
+            } else {
+                Role::None
+            }
+        }
+
+        /// Get the amount the caller can claim now, 0 if the caller is not a beneficiary
+        #[ink(message)]
+        pub fn my_claimable(&self) -> Balance {
+            self.get_amount_to_claim(self.env().caller()).unwrap_or(0)
+        }
+
         //----------------------------------------------------------------------------------------
         // Internal functions
         //----------------------------------------------------------------------------------------
@@ -960,6 +2464,14 @@ mod open_payroll {
             Ok(())
         }
 
+        // ensure_contract_not_frozen ensures that the whole contract is not frozen
+        fn ensure_contract_not_frozen(&self) -> Result<(), Error> {
+            if self.contract_frozen {
+                return Err(Error::ContractFrozen);
+            }
+            Ok(())
+        }
+
         // ensure_is_not_paused ensures that the contract is not paused
         fn ensure_is_not_paused(&self) -> Result<(), Error> {
             if self.is_paused() {
@@ -969,27 +2481,65 @@ mod open_payroll {
         }
 
         // Ensure multipliers are valid
+        // Deactivated multipliers are accepted until their validity ends only if they are in the stored multipliers
         fn ensure_multipliers_are_valid(
             &self,
             multipliers: &[(MultiplierId, Multiplier)],
+            stored_multipliers: &BTreeMap<MultiplierId, Multiplier>,
         ) -> Result<(), Error> {
+            let current_block = self.env().block_number();
             for (multiplier_id, _) in multipliers.iter() {
-                if !self.base_multipliers.contains(multiplier_id) {
-                    return Err(Error::MultiplierNotFound);
-                }
-                if self
+                let base_multiplier = self
                     .base_multipliers
                     .get(multiplier_id)
-                    .unwrap()
-                    .valid_until_block
-                    .is_some()
-                {
-                    return Err(Error::MultiplierAlreadyDeactivated);
+                    .ok_or(Error::MultiplierNotFound)?;
+
+                match base_multiplier.valid_until_block {
+                    Some(_) if !stored_multipliers.contains_key(multiplier_id) => {
+                        return Err(Error::MultiplierAlreadyDeactivated)
+                    }
+                    Some(valid_until_block) if valid_until_block <= current_block => {
+                        return Err(Error::MultiplierExpired)
+                    }
+                    _ => {}
+                }
+            }
+            self.ensure_multiplier_values_in_ratio(multipliers)
+        }
+
+        // Ensure the multiplier values are not above the max ratio of the multiplier base, if set
+        fn ensure_multiplier_values_in_ratio(
+            &self,
+            multipliers: &[(MultiplierId, Multiplier)],
+        ) -> Result<(), Error> {
+            if let Some(ratio) = self.max_multiplier_ratio {
+                let max_value = ratio.saturating_mul(MULTIPLIER_BASE);
+                if multipliers.iter().any(|(_, value)| *value > max_value) {
+                    return Err(Error::MultiplierValueTooLarge);
                 }
             }
             Ok(())
         }
 
+        // Ensure there is an active multiplier other than the given one, if the contract requires it
+        fn ensure_active_multiplier_left(&self, multiplier_id: MultiplierId) -> Result<(), Error> {
+            if !self.require_min_one_multiplier {
+                return Ok(());
+            }
+
+            let any_active_left = self.multipliers_list.iter().any(|id| {
+                *id != multiplier_id
+                    && self
+                        .base_multipliers
+                        .get(id)
+                        .map_or(false, |m| m.valid_until_block.is_none())
+            });
+            if !any_active_left {
+                return Err(Error::WouldLeaveNoMultipliers);
+            }
+            Ok(())
+        }
+
         // Function for doing the ensurance before adding a new beneficiary
         fn ensure_beneficiary_to_add(
             &self,
@@ -1009,37 +2559,109 @@ mod open_payroll {
             }
 
             // Ensure that the multipliers are valid
-            self.ensure_multipliers_are_valid(multipliers)?;
+            self.ensure_multipliers_are_valid(multipliers, &BTreeMap::new())?;
+            ensure_no_duplicate_multipliers(&Vec::from(multipliers))?;
+
+            Ok(())
+        }
+
+        // Function for doing the ensurance before updating a beneficiary
+        fn ensure_beneficiary_to_update(
+            &self,
+            account_id: AccountId,
+            multipliers: &[(MultiplierId, Multiplier)],
+        ) -> Result<(), Error> {
+            // Ensure that the beneficiary exists
+            let beneficiary = self
+                .beneficiaries
+                .get(account_id)
+                .ok_or(Error::AccountNotFound)?;
+
+            // Check that the multipliers are valid, a beneficiary can keep a deactivated one until it expires
+            self.ensure_multipliers_are_valid(multipliers, &beneficiary.multipliers)?;
             ensure_no_duplicate_multipliers(&Vec::from(multipliers))?;
 
             Ok(())
         }
 
         // Get the amount of tokens that can be claimed by a beneficiary with specific block_numer
+        // A deactivated multiplier counts in the periods that start before its valid_until_block
         fn _get_amount_to_claim_in_block(
             &self,
             account_id: AccountId,
-            filtered_multipliers: bool,
             block: BlockNumber,
         ) -> Balance {
             // The check that beneficiary exists is done in the caller function
             let beneficiary = self.beneficiaries.get(account_id).unwrap();
 
+            // Nothing accrues after the contract entered the closing state
+            let block = match self.closed_at_block {
+                Some(closed_at_block) if closed_at_block < block => {
+                    closed_at_block.max(beneficiary.last_updated_period_block)
+                }
+                _ => block,
+            };
+
             // Calculates the number of blocks that have elapsed since the last payment
-            let blocks_since_last_payment = block - beneficiary.last_updated_period_block;
+            // nothing accrues while the last payment block is ahead of the block
+            let blocks_since_last_payment =
+                block.saturating_sub(beneficiary.last_updated_period_block);
 
             // Calculates the number of periods that are due based on the elapsed blocks
-            let unclaimed_periods: u128 = (blocks_since_last_payment / self.periodicity).into();
+            let periodicity = self._get_beneficiary_periodicity(&beneficiary);
+            let mut unclaimed_periods = blocks_since_last_payment / periodicity;
+            if let Some(max_unclaimed_periods) = self.max_unclaimed_periods {
+                unclaimed_periods = unclaimed_periods.min(max_unclaimed_periods);
+            }
 
             // If there's no unclaimed periods, return the unclaimed payments
             // Otherwise, calculate the amount to claim and add the unclaimed payments
             if unclaimed_periods == 0 {
                 beneficiary.unclaimed_payments
             } else {
-                let payment_per_period =
-                    self._get_amount_to_claim_for_one_period(&beneficiary, filtered_multipliers);
+                let from_block = beneficiary.last_updated_period_block;
+                // The cached sum is paid in every period while no deactivated multiplier applies
+                let multipliers = match beneficiary.cached_multiplier_sum {
+                    Some(_) => Vec::new(),
+                    None => self._get_multipliers_validity(&beneficiary),
+                };
+
+                // Split the periods at the first period starting once a deactivated multiplier stopped counting
+                let mut splits: Vec<u32> = multipliers
+                    .iter()
+                    .filter_map(|(_, valid_until_block)| *valid_until_block)
+                    .filter(|valid_until_block| *valid_until_block > from_block)
+                    .map(|valid_until_block| {
+                        let blocks = valid_until_block - from_block;
+                        (blocks / periodicity + u32::from(blocks % periodicity != 0))
+                            .min(unclaimed_periods)
+                    })
+                    .collect();
+                splits.push(0);
+                splits.push(unclaimed_periods);
+                splits.sort_unstable();
+                splits.dedup();
+
+                let mut total = beneficiary.unclaimed_payments;
+                for segment in splits.windows(2) {
+                    let segment_block = from_block + segment[0] * periodicity;
+                    let final_multiplier =
+                        self._get_final_multiplier_at(&beneficiary, &multipliers, segment_block);
+
+                    // Each period is paid with the base payment in effect when it started
+                    for (base_payment, periods) in self._get_base_payments_for_periods(
+                        segment_block,
+                        periodicity,
+                        segment[1] - segment[0],
+                    ) {
+                        total = total.saturating_add(
+                            (final_multiplier.saturating_mul(base_payment) / MULTIPLIER_BASE)
+                                .saturating_mul(u128::from(periods)),
+                        );
+                    }
+                }
 
-                payment_per_period * unclaimed_periods + beneficiary.unclaimed_payments
+                total
             }
         }
 
@@ -1049,42 +2671,211 @@ mod open_payroll {
             &self,
             beneficiary: &Beneficiary,
             filtered_multipliers: bool,
+        ) -> Balance {
+            self._get_amount_for_one_period_with_base_payment(
+                beneficiary,
+                filtered_multipliers,
+                self.base_payment,
+            )
+        }
+
+        // check the amount to claim for one beneficiary in one period with a given base payment
+        // saturating at the maximum balance instead of overflowing
+        fn _get_amount_for_one_period_with_base_payment(
+            &self,
+            beneficiary: &Beneficiary,
+            filtered_multipliers: bool,
+            base_payment: Balance,
         ) -> Balance {
             // E.g (M1 + M2) * B / 100
-            // Sum all active multipliers
-            let final_multiplier: u128 = if beneficiary.multipliers.is_empty() {
-                1
+            self._get_final_multiplier(beneficiary, filtered_multipliers)
+                .saturating_mul(base_payment)
+                / MULTIPLIER_BASE
+        }
+
+        // Get the periodicity of a beneficiary, its override or the contract one
+        fn _get_beneficiary_periodicity(&self, beneficiary: &Beneficiary) -> u32 {
+            beneficiary.periodicity_override.unwrap_or(self.periodicity)
+        }
+
+        // Get the initial block of the current period of a beneficiary, using its periodicity
+        fn _get_beneficiary_period_initial_block(&self, beneficiary: &Beneficiary) -> BlockNumber {
+            let current_block = self.env().block_number();
+            current_block
+                - ((current_block - self.initial_block)
+                    % self._get_beneficiary_periodicity(beneficiary))
+        }
+
+        // Get the block a settled beneficiary accrues from after a periodicity change, its period start
+        // or the next one if it starts before the settled block, so the last payment block never moves backwards
+        fn _get_beneficiary_accrual_start_block(&self, beneficiary: &Beneficiary) -> BlockNumber {
+            let period_block = self._get_beneficiary_period_initial_block(beneficiary);
+            if period_block < beneficiary.last_updated_period_block {
+                period_block + self._get_beneficiary_periodicity(beneficiary)
+            } else {
+                period_block
+            }
+        }
+
+        // Sum the stored unclaimed payments of every beneficiary except the given one
+        fn _get_unclaimed_payments_of_others(&self, account_id: AccountId) -> Balance {
+            self.beneficiaries_accounts
+                .iter()
+                .filter(|other| **other != account_id)
+                .map(|other| self.beneficiaries.get(other).unwrap().unclaimed_payments)
+                .sum()
+        }
+
+        // Sum all active multipliers of a beneficiary, set by the empty multiplier policy if it has no multipliers
+        fn _get_final_multiplier(
+            &self,
+            beneficiary: &Beneficiary,
+            filtered_multipliers: bool,
+        ) -> u128 {
+            if beneficiary.multipliers.is_empty() {
+                match self.empty_multiplier_policy {
+                    EmptyPolicy::PayFraction => 1,
+                    EmptyPolicy::PayNothing => 0,
+                    EmptyPolicy::PayFullBase => MULTIPLIER_BASE,
+                }
             } else {
                 match filtered_multipliers {
                     true => beneficiary.multipliers.values().sum(),
-                    _ => beneficiary
-                        .multipliers
-                        .iter()
-                        .filter(|(k, _)| {
-                            self.base_multipliers
-                                .get(k)
-                                .unwrap()
-                                .valid_until_block
-                                .is_none()
-                        })
-                        .map(|(_, v)| v)
-                        .sum(),
+                    _ => beneficiary.cached_multiplier_sum.unwrap_or_else(|| {
+                        self._get_active_multipliers_sum(&beneficiary.multipliers)
+                    }),
                 }
+            }
+        }
+
+        // Get the value of each multiplier of a beneficiary with the block until which it counts, None if it is active
+        // A deleted multiplier no longer counts at all
+        fn _get_multipliers_validity(
+            &self,
+            beneficiary: &Beneficiary,
+        ) -> Vec<(Multiplier, Option<BlockNumber>)> {
+            beneficiary
+                .multipliers
+                .iter()
+                .map(|(id, value)| match self.base_multipliers.get(id) {
+                    Some(base_multiplier) => (*value, base_multiplier.valid_until_block),
+                    None => (*value, Some(0)),
+                })
+                .collect()
+        }
+
+        // Sum the multipliers of a beneficiary that count in the period starting at the given block,
+        // set by the empty multiplier policy if it has no multipliers
+        fn _get_final_multiplier_at(
+            &self,
+            beneficiary: &Beneficiary,
+            multipliers: &[(Multiplier, Option<BlockNumber>)],
+            block: BlockNumber,
+        ) -> u128 {
+            if multipliers.is_empty() {
+                return self._get_final_multiplier(beneficiary, false);
+            }
+
+            multipliers
+                .iter()
+                .filter(|(_, valid_until_block)| {
+                    valid_until_block.map_or(true, |until| block < until)
+                })
+                .map(|(value, _)| value)
+                .sum()
+        }
+
+        // Split a number of periods starting at from_block by the base payment in effect at the start of each period
+        // Returns a list of (base_payment, number of periods)
+        fn _get_base_payments_for_periods(
+            &self,
+            from_block: BlockNumber,
+            periodicity: u32,
+            periods: u32,
+        ) -> Vec<(Balance, u32)> {
+            // Index of the first period starting at or after the given block
+            let first_period_from = |block: BlockNumber| -> u32 {
+                if block <= from_block {
+                    return 0;
+                }
+                let elapsed = u64::from(block - from_block);
+                let periodicity = u64::from(periodicity);
+                (elapsed / periodicity + u64::from(elapsed % periodicity != 0))
+                    .min(u64::from(periods)) as u32
             };
 
-            final_multiplier * self.base_payment / 100
+            let mut base_payments = Vec::new();
+            for (i, (block, base_payment)) in self.base_payment_history.iter().enumerate() {
+                let start = first_period_from(*block);
+                let end = match self.base_payment_history.get(i + 1) {
+                    Some((next_block, _)) => first_period_from(*next_block),
+                    None => periods,
+                };
+                if end > start {
+                    base_payments.push((*base_payment, end - start));
+                }
+            }
+
+            base_payments
         }
 
-        // internal function to get the amount to claim
-        // filtered multipliers in true means that all multipliers are active
-        fn _get_amount_to_claim(
+        // Sum the multipliers that are still active, skipping the deactivated or deleted ones
+        fn _get_active_multipliers_sum(
             &self,
-            account_id: AccountId,
-            filtered_multipliers: bool,
-        ) -> Balance {
+            multipliers: &BTreeMap<MultiplierId, Multiplier>,
+        ) -> u128 {
+            multipliers
+                .iter()
+                .filter(|(k, _)| self._is_multiplier_active(**k))
+                .map(|(_, v)| v)
+                .sum()
+        }
+
+        // Get the sum to cache for the multipliers of a beneficiary, None if any of them is deactivated or deleted
+        fn _get_multipliers_cache(
+            &self,
+            multipliers: &BTreeMap<MultiplierId, Multiplier>,
+        ) -> Option<u128> {
+            if multipliers.keys().all(|k| self._is_multiplier_active(*k)) {
+                Some(multipliers.values().sum())
+            } else {
+                None
+            }
+        }
+
+        // Check if a base multiplier exists and is not deactivated
+        fn _is_multiplier_active(&self, multiplier_id: MultiplierId) -> bool {
+            self.base_multipliers
+                .get(multiplier_id)
+                .map_or(false, |multiplier| multiplier.valid_until_block.is_none())
+        }
+
+        // Recompute the cached sum of multipliers of every beneficiary
+        fn _refresh_multipliers_cache(&mut self) {
+            for account_id in self.beneficiaries_accounts.iter() {
+                let mut beneficiary = self.beneficiaries.get(account_id).unwrap();
+                beneficiary.cached_multiplier_sum =
+                    self._get_multipliers_cache(&beneficiary.multipliers);
+                self.beneficiaries.insert(account_id, &beneficiary);
+            }
+        }
+
+        // internal function to get the amount to claim
+        fn _get_amount_to_claim(&self, account_id: AccountId) -> Balance {
             let current_block = self.env().block_number();
 
-            self._get_amount_to_claim_in_block(account_id, filtered_multipliers, current_block)
+            self._get_amount_to_claim_in_block(account_id, current_block)
+        }
+
+        // Count the claim of a beneficiary in the contract period, once per beneficiary and period
+        // The contract period is used whatever the periodicity of the beneficiary, as it is the one checked
+        // before updating the schedule. The caller stores the beneficiary.
+        fn _count_claim_in_period(&mut self, beneficiary: &mut Beneficiary) {
+            let current_period_block = self.get_current_period_initial_block();
+            if beneficiary.last_claim_period_block != current_period_block {
+                beneficiary.last_claim_period_block = current_period_block;
+                self._update_claims_in_period(current_period_block);
+            }
         }
 
         // Updates the number of claims in a period
@@ -1095,25 +2886,95 @@ mod open_payroll {
                 // Updates current claims in period
                 self.claims_in_period.total_claims += 1;
             } else {
+                // Record the total paid up to the end of the previous period with claims
+                if self.claims_in_period.total_claims > 0 {
+                    self.total_paid_at_period
+                        .insert(self.claims_in_period.period, &self.total_paid);
+                }
+
                 // Reset the claims in period
                 self.claims_in_period.period = claiming_period_block;
                 self.claims_in_period.total_claims = 1;
             }
         }
 
-        // Ensure if all beneficiaries claimed in period
-        fn ensure_all_claimed_in_period(&mut self) -> Result<(), Error> {
-            let claiming_period_block = self.get_current_period_initial_block();
+        // Settles the unclaimed payments and sets the new multipliers of a beneficiary, which must exist
+        fn _update_beneficiary(
+            &mut self,
+            account_id: AccountId,
+            multipliers: Vec<(MultiplierId, Multiplier)>,
+        ) {
+            let beneficiary = self.beneficiaries.get(account_id).unwrap();
 
-            let claims_in_period = self.claims_in_period.clone();
+            let multipliers_vec = multipliers.clone();
+            let multipliers = vec_to_btreemap(&multipliers);
+            let cached_multiplier_sum = self._get_multipliers_cache(&multipliers);
 
-            if (claiming_period_block == claims_in_period.period
-                && claims_in_period.total_claims == self.beneficiaries_accounts.len() as u32)
-                || claiming_period_block == 0
-            // initial period in intial block noone can claim
-            {
-                return Ok(());
-            }
+            // calculate the amount to claim to be transferred to the uncleared payments
+            let unclaimed_payments = self._get_amount_to_claim(account_id);
+
+            // update de beneficiary with new multipliers and new unclaimed payments
+            self.beneficiaries.insert(
+                account_id,
+                &Beneficiary {
+                    multipliers,
+                    cached_multiplier_sum,
+                    unclaimed_payments,
+                    last_updated_period_block: self
+                        ._get_beneficiary_period_initial_block(&beneficiary),
+                    ..beneficiary
+                },
+            );
+
+            // Emit the BeneficiaryUpdated event
+            self.env().emit_event(BeneficiaryUpdated {
+                account_id,
+                multipliers_vec,
+            });
+        }
+
+        // Moves the accrued amount of a beneficiary into unclaimed_payments and sets the
+        // last_updated_period_block to the current period, counting it as a claim in the period
+        // The check that beneficiary exists is done in the caller function
+        fn _settle_accrual(&mut self, account_id: AccountId) -> Balance {
+            let mut beneficiary = self.beneficiaries.get(account_id).unwrap();
+            let claiming_period_block = self._get_beneficiary_period_initial_block(&beneficiary);
+            let unclaimed_payments = self._get_amount_to_claim(account_id);
+
+            self._count_claim_in_period(&mut beneficiary);
+
+            beneficiary.unclaimed_payments = unclaimed_payments;
+            beneficiary.last_updated_period_block = claiming_period_block;
+            self.beneficiaries.insert(account_id, &beneficiary);
+
+            unclaimed_payments
+        }
+
+        // Enters the closing state, keeping the first closing block
+        fn _enter_closing(&mut self) {
+            if self.closed_at_block.is_none() {
+                self.closed_at_block = Some(self.env().block_number());
+            }
+        }
+
+        // Ensure if all beneficiaries claimed in period
+        fn ensure_all_claimed_in_period(&self) -> Result<(), Error> {
+            // Without beneficiaries there is nothing to claim
+            if self.beneficiaries_accounts.is_empty() {
+                return Ok(());
+            }
+
+            let claiming_period_block = self.get_current_period_initial_block();
+
+            let claims_in_period = self.claims_in_period.clone();
+
+            if (claiming_period_block == claims_in_period.period
+                && claims_in_period.total_claims == self.beneficiaries_accounts.len() as u32)
+                || claiming_period_block == 0
+            // initial period in intial block noone can claim
+            {
+                return Ok(());
+            }
 
             Err(Error::NotAllClaimedInPeriod)
         }
@@ -1123,6 +2984,30 @@ mod open_payroll {
     // Pure functions
     //----------------------------------------------------------------------------------------
 
+    /// Replace the multiplier names of the beneficiaries with the ids the constructor gives to the base multipliers,
+    /// which are their positions in the list
+    fn resolve_multiplier_names(
+        base_multipliers: &[String],
+        beneficiaries: Vec<NamedInitialBeneficiary>,
+    ) -> Result<Vec<InitialBeneficiary>, Error> {
+        let mut resolved = Vec::new();
+        for beneficiary in beneficiaries.into_iter() {
+            let mut multipliers = Vec::new();
+            for (name, value) in beneficiary.multipliers.iter() {
+                let id = base_multipliers
+                    .iter()
+                    .position(|base_name| base_name == name)
+                    .ok_or(Error::MultiplierNotFound)?;
+                multipliers.push((id as MultiplierId, *value));
+            }
+            resolved.push(InitialBeneficiary {
+                account_id: beneficiary.account_id,
+                multipliers,
+            });
+        }
+        Ok(resolved)
+    }
+
     /// Given a vector of (id, multiplier) pairs, return a BTreeMap of (id, multiplier) pairs
     fn vec_to_btreemap(vec: &[(MultiplierId, Multiplier)]) -> BTreeMap<MultiplierId, Multiplier> {
         let mut btree_map = BTreeMap::new();
@@ -1132,6 +3017,32 @@ mod open_payroll {
         btree_map
     }
 
+    /// Given a target duration in seconds and the average block time in milliseconds, return the
+    /// closest amount of blocks. Returns 0 if the block time is 0 and saturates at u32::MAX
+    fn suggest_periodicity(target_seconds: u64, avg_block_time_ms: u64) -> u32 {
+        if avg_block_time_ms == 0 {
+            return 0;
+        }
+
+        let target_ms = target_seconds.saturating_mul(1000);
+        let blocks = target_ms.saturating_add(avg_block_time_ms / 2) / avg_block_time_ms;
+
+        u32::try_from(blocks).unwrap_or(u32::MAX)
+    }
+
+    /// Given an account it checks if it is the zero (burn) address
+    fn is_zero_address(account_id: &AccountId) -> bool {
+        *account_id == AccountId::from([0u8; 32])
+    }
+
+    /// Given a string it ensures it is not longer than MAX_STRING_LEN
+    fn ensure_string_length(value: &str) -> Result<(), Error> {
+        if value.len() > MAX_STRING_LEN {
+            return Err(Error::StringTooLong);
+        }
+        Ok(())
+    }
+
     /// Given a list of beneficiaries it ensures there are no duplicates
     #[allow(clippy::all)]
     fn ensure_no_duplicate_beneficiaries(beneficiaries: &Vec<AccountId>) -> Result<(), Error> {
@@ -1176,6 +3087,8 @@ mod open_payroll {
 
         use super::*;
 
+        type Event = <OpenPayroll as ::ink::reflect::ContractEventBase>::Type;
+
         // UTILITY FUNCTIONS TO MAKE TESTING EASIER
         fn create_contract(
             initial_balance: Balance,
@@ -1195,6 +3108,10 @@ mod open_payroll {
                 1000,
                 vec!["Seniority".to_string(), "Performance".to_string()],
                 vec![beneficiary_bob, beneficiary_charlie],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
             )
             .expect("Cannot create contract")
         }
@@ -1206,6 +3123,10 @@ mod open_payroll {
                 1000,
                 vec!["Seniority".to_string(), "Performance".to_string()],
                 vec![],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
             )
             .expect("Cannot create contract")
         }
@@ -1220,6 +3141,10 @@ mod open_payroll {
                 1000,
                 vec!["Seniority".to_string(), "Performance".to_string()],
                 vec![],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
             )
             .expect("Cannot create contract")
         }
@@ -1268,6 +3193,15 @@ mod open_payroll {
                 .expect("Cannot get account balance")
         }
 
+        fn recorded_events() -> Vec<Event> {
+            ink::env::test::recorded_events()
+                .map(|event| {
+                    <Event as scale::Decode>::decode(&mut &event.data[..])
+                        .expect("Cannot decode event")
+                })
+                .collect()
+        }
+
         fn vec_to_btreemap(
             vec: &[(MultiplierId, Multiplier)],
         ) -> BTreeMap<MultiplierId, Multiplier> {
@@ -1302,8 +3236,12 @@ mod open_payroll {
                 1000,
                 vec!["Seniority".to_string(), "Performance".to_string()],
                 vec![beneficiary_bob, beneficiary_charlie],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
             );
-            assert!(matches!(res, Ok(_)));
+            assert!(res.is_ok());
             let contract = res.unwrap();
 
             // check that base_multipliers are set correctly
@@ -1332,8 +3270,13 @@ mod open_payroll {
                 Beneficiary {
                     account_id: accounts.bob,
                     multipliers: vec_to_btreemap(&[(0, 100), (1, 3)]),
+                    cached_multiplier_sum: Some(103),
                     unclaimed_payments: 0,
                     last_updated_period_block: 0,
+                    periodicity_override: None,
+                    frozen: false,
+                    added_at_block: 0,
+                    last_claim_period_block: 0,
                 }
             );
             assert_eq!(
@@ -1341,14 +3284,19 @@ mod open_payroll {
                 Beneficiary {
                     account_id: accounts.charlie,
                     multipliers: vec_to_btreemap(&[(0, 100), (1, 10)]),
+                    cached_multiplier_sum: Some(110),
                     unclaimed_payments: 0,
                     last_updated_period_block: 0,
+                    periodicity_override: None,
+                    frozen: false,
+                    added_at_block: 0,
+                    last_claim_period_block: 0,
                 }
             );
 
             // check accounts are set correctly
             assert_eq!(
-                contract.beneficiaries_accounts.get(0).unwrap(),
+                contract.beneficiaries_accounts.first().unwrap(),
                 &accounts.bob
             );
             assert_eq!(
@@ -1382,6 +3330,10 @@ mod open_payroll {
                 1000,
                 vec!["Seniority".to_string(), "Performance".to_string()],
                 vec![beneficiary_bob, beneficiary_charlie],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
             );
 
             assert!(matches!(res, Err(Error::InvalidMultipliersLength)));
@@ -1399,6 +3351,10 @@ mod open_payroll {
                 1000,
                 vec!["Seniority".to_string(), "Performance".to_string()],
                 vec![beneficiary_bob, beneficiary_charlie],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
             );
 
             assert!(matches!(res, Err(Error::InvalidMultipliersLength)));
@@ -1416,6 +3372,10 @@ mod open_payroll {
                 1000,
                 vec!["Seniority".to_string(), "Performance".to_string()],
                 vec![beneficiary_bob, beneficiary_charlie],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
             );
 
             assert!(matches!(res, Err(Error::InvalidMultipliersLength)));
@@ -1437,6 +3397,10 @@ mod open_payroll {
                     "Years_at_company".to_string(),
                 ],
                 vec![beneficiary_bob, beneficiary_charlie],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
             );
 
             assert!(matches!(res, Err(Error::InvalidMultipliersLength)));
@@ -1458,6 +3422,10 @@ mod open_payroll {
                 1000,
                 vec!["Seniority".to_string(), "Performance".to_string()],
                 vec![beneficiary_1, beneficiary_2],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
             );
 
             assert!(matches!(res, Err(Error::DuplicatedBeneficiaries)));
@@ -1494,7 +3462,7 @@ mod open_payroll {
 
             // check if account was added to the vector
             assert_eq!(
-                contract.beneficiaries_accounts.get(0).unwrap(),
+                contract.beneficiaries_accounts.first().unwrap(),
                 &accounts.bob
             );
         }
@@ -1520,10 +3488,7 @@ mod open_payroll {
             let accounts = default_accounts();
             set_sender(accounts.alice);
             let mut contract = create_contract_with_no_beneficiaries(100_000_000u128);
-            assert!(matches!(
-                contract.add_beneficiary(accounts.bob, vec![]),
-                Ok(_)
-            ));
+            assert!(contract.add_beneficiary(accounts.bob, vec![]).is_ok());
         }
 
         /// Remove a beneficiary and check that it is removed
@@ -1537,7 +3502,7 @@ mod open_payroll {
                 .unwrap();
             assert_eq!(contract.beneficiaries_accounts.len(), 1);
             assert_eq!(
-                contract.beneficiaries_accounts.get(0).unwrap(),
+                contract.beneficiaries_accounts.first().unwrap(),
                 &accounts.bob
             );
             assert_eq!(
@@ -1570,7 +3535,7 @@ mod open_payroll {
             ));
             assert_eq!(contract.beneficiaries_accounts.len(), 1);
             assert_eq!(
-                contract.beneficiaries_accounts.get(0).unwrap(),
+                contract.beneficiaries_accounts.first().unwrap(),
                 &accounts.bob
             );
         }
@@ -1643,7 +3608,7 @@ mod open_payroll {
         #[ink::test]
         fn update_periodicity() {
             let (_, mut contract) = create_accounts_and_contract(100_000_000u128);
-            contract.update_periodicity(100u32).unwrap();
+            contract.update_periodicity(100u32, false).unwrap();
             assert_eq!(contract.periodicity, 100u32);
         }
 
@@ -1653,7 +3618,7 @@ mod open_payroll {
             let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
             set_sender(accounts.bob);
             assert!(matches!(
-                contract.update_periodicity(100u32),
+                contract.update_periodicity(100u32, false),
                 Err(Error::NotOwner)
             ));
         }
@@ -1664,7 +3629,7 @@ mod open_payroll {
             let (_, mut contract) = create_accounts_and_contract(100_000_000u128);
 
             assert!(matches!(
-                contract.update_periodicity(0u32),
+                contract.update_periodicity(0u32, false),
                 Err(Error::InvalidParams)
             ));
         }
@@ -1675,7 +3640,7 @@ mod open_payroll {
             let starting_block = get_current_block();
             let (_, mut contract) = create_accounts_and_contract(100_000_000u128);
 
-            contract.pause().unwrap();
+            contract.pause(None).unwrap();
             assert!(contract.is_paused());
             advance_n_blocks(1);
             contract.resume().unwrap();
@@ -1689,7 +3654,7 @@ mod open_payroll {
         fn pause_and_resume_without_access() {
             let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
             set_sender(accounts.bob);
-            assert!(matches!(contract.pause(), Err(Error::NotOwner)));
+            assert!(matches!(contract.pause(None), Err(Error::NotOwner)));
             assert!(matches!(contract.resume(), Err(Error::NotOwner)));
         }
 
@@ -1709,7 +3674,7 @@ mod open_payroll {
 
             let amount_to_claim = contract.get_amount_to_claim(accounts.bob).unwrap();
             contract
-                .claim_payment(accounts.bob, amount_to_claim)
+                .claim_payment(accounts.bob, amount_to_claim, None)
                 .unwrap();
             assert!(get_balance(contract.owner) < contract_balance_before_payment);
             assert!(get_balance(accounts.bob) > bob_balance_before_payment);
@@ -1733,7 +3698,7 @@ mod open_payroll {
 
             let amount_to_claim = contract.get_amount_to_claim(accounts.bob).unwrap();
             contract
-                .claim_payment(accounts.bob, amount_to_claim - total_not_claimed)
+                .claim_payment(accounts.bob, amount_to_claim - total_not_claimed, None)
                 .unwrap();
             assert!(
                 get_balance(contract.owner) == total_amount - amount_to_claim + total_not_claimed
@@ -1768,7 +3733,7 @@ mod open_payroll {
             set_sender(accounts.bob);
 
             let amount_to_claim = contract.get_amount_to_claim(accounts.bob).unwrap();
-            let res = contract.claim_payment(accounts.bob, amount_to_claim + 1);
+            let res = contract.claim_payment(accounts.bob, amount_to_claim + 1, None);
 
             assert!(matches!(
                 res,
@@ -1789,7 +3754,7 @@ mod open_payroll {
             // advance 3 blocks so a payment will be claimable
             advance_n_blocks(3);
 
-            let res = contract.update_periodicity(10u32);
+            let res = contract.update_periodicity(10u32, false);
             assert!(matches!(res, Err(Error::NotAllClaimedInPeriod)));
         }
 
@@ -1806,9 +3771,9 @@ mod open_payroll {
             advance_n_blocks(3);
 
             // When you claim a payment with 0 amount, it will calculate the amount to claim an set it to unclaim payments.
-            contract.claim_payment(accounts.bob, 0).unwrap();
+            contract.claim_payment(accounts.bob, 0, None).unwrap();
 
-            let res = contract.update_periodicity(10u32);
+            let res = contract.update_periodicity(10u32, false);
 
             assert!(matches!(res, Ok(())));
         }
@@ -1829,11 +3794,11 @@ mod open_payroll {
 
             let amount_to_claim = contract.get_amount_to_claim(accounts.bob).unwrap();
             contract
-                .claim_payment(accounts.bob, amount_to_claim)
+                .claim_payment(accounts.bob, amount_to_claim, None)
                 .unwrap();
 
             set_sender(accounts.alice);
-            let res = contract.update_periodicity(10u32);
+            let res = contract.update_periodicity(10u32, false);
 
             assert_eq!(res, Ok(()));
         }
@@ -1870,7 +3835,7 @@ mod open_payroll {
             set_sender(accounts.bob);
             let amount_to_claim = contract.get_amount_to_claim(accounts.bob).unwrap();
             contract
-                .claim_payment(accounts.bob, amount_to_claim)
+                .claim_payment(accounts.bob, amount_to_claim, None)
                 .unwrap();
 
             set_sender(accounts.alice);
@@ -1926,7 +3891,7 @@ mod open_payroll {
 
             advance_n_blocks(6);
 
-            let res = contract.deactivate_multiplier(1);
+            let res = contract.deactivate_multiplier(1, false);
 
             advance_n_blocks(5);
 
@@ -2005,7 +3970,7 @@ mod open_payroll {
             set_sender(accounts.bob);
             let amount_to_claim = contract.get_amount_to_claim(accounts.bob).unwrap();
             contract
-                .claim_payment(accounts.bob, amount_to_claim)
+                .claim_payment(accounts.bob, amount_to_claim, None)
                 .unwrap();
 
             // check final amount
@@ -2046,7 +4011,7 @@ mod open_payroll {
             set_sender(accounts.bob);
             let amount_to_claim = contract.get_amount_to_claim(accounts.bob).unwrap();
             contract
-                .claim_payment(accounts.bob, amount_to_claim)
+                .claim_payment(accounts.bob, amount_to_claim, None)
                 .unwrap();
 
             let unclaimed_beneficiaries = contract.get_unclaimed_beneficiaries();
@@ -2107,11 +4072,11 @@ mod open_payroll {
             // claim bob and charlie, then check if debt is 0
             set_sender(accounts.bob);
             contract
-                .claim_payment(accounts.bob, bob_amount_claim)
+                .claim_payment(accounts.bob, bob_amount_claim, None)
                 .unwrap();
             set_sender(accounts.charlie);
             contract
-                .claim_payment(accounts.charlie, charlie_amount_claim)
+                .claim_payment(accounts.charlie, charlie_amount_claim, None)
                 .unwrap();
 
             assert_eq!(contract.get_total_debts(), 0);
@@ -2186,7 +4151,7 @@ mod open_payroll {
 
             // change owner to bob
             set_sender(accounts.alice);
-            let transfer_ownership_result = contract.propose_transfer_ownership(accounts.bob);
+            let transfer_ownership_result = contract.propose_transfer_ownership(accounts.bob, 0);
             assert!(transfer_ownership_result.is_ok());
 
             // check if owner is bob
@@ -2222,6 +4187,10 @@ mod open_payroll {
                 1000,
                 vec!["Seniority".to_string(), "Performance".to_string()],
                 beneficiaries,
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
             );
 
             assert!(matches!(res, Err(Error::MaxBeneficiariesExceeded)));
@@ -2243,7 +4212,16 @@ mod open_payroll {
                 multipliers: vec![],
             };
 
-            let res = OpenPayroll::new(2, 1000, multipliers, vec![beneficiary]);
+            let res = OpenPayroll::new(
+                2,
+                1000,
+                multipliers,
+                vec![beneficiary],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            );
 
             assert!(matches!(res, Err(Error::MaxMultipliersExceeded)));
         }
@@ -2265,5 +4243,2571 @@ mod open_payroll {
 
             assert!(matches!(res, Err(Error::MaxMultipliersExceeded)));
         }
+
+        /// Settle the accrual of a beneficiary after several periods
+        #[ink::test]
+        fn settle_accrual() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            // advance 3 periods without claiming
+            advance_n_blocks(6);
+
+            let amount_to_claim = contract.get_amount_to_claim(accounts.bob).unwrap();
+            let settled = contract.settle_accrual(accounts.bob).unwrap();
+            assert_eq!(settled, amount_to_claim);
+
+            let beneficiary = contract.beneficiaries.get(accounts.bob).unwrap();
+            assert_eq!(beneficiary.unclaimed_payments, amount_to_claim);
+            assert_eq!(
+                beneficiary.last_updated_period_block,
+                contract.get_current_period_initial_block()
+            );
+
+            // nothing new is accrued in the same period
+            assert_eq!(
+                contract.get_amount_to_claim(accounts.bob),
+                Some(amount_to_claim)
+            );
+
+            // accrual restarts from the settled period
+            advance_n_blocks(2);
+            assert_eq!(
+                contract.get_amount_to_claim(accounts.bob),
+                Some(amount_to_claim + 1030)
+            );
+
+            // no funds were transferred
+            assert_eq!(get_balance(contract_id()), 100_000_000u128);
+        }
+
+        /// Settle the accrual fails without access or for an unknown account
+        #[ink::test]
+        fn settle_accrual_errors() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert_eq!(
+                contract.settle_accrual(accounts.django),
+                Err(Error::AccountNotFound)
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(contract.settle_accrual(accounts.bob), Err(Error::NotOwner));
+        }
+
+        /// Settle all the beneficiaries and update the base payment in the middle of a period
+        #[ink::test]
+        fn settle_all_accrual() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            // advance 3 blocks so a payment will be claimable
+            advance_n_blocks(3);
+
+            assert_eq!(
+                contract.update_base_payment(2000),
+                Err(Error::NotAllClaimedInPeriod)
+            );
+
+            let bob_amount = contract.get_amount_to_claim(accounts.bob).unwrap();
+            let charlie_amount = contract.get_amount_to_claim(accounts.charlie).unwrap();
+
+            contract.settle_all_accrual().unwrap();
+
+            assert_eq!(
+                contract.claims_in_period,
+                ClaimsInPeriod {
+                    period: 2,
+                    total_claims: 2,
+                }
+            );
+            assert_eq!(
+                contract
+                    .beneficiaries
+                    .get(accounts.bob)
+                    .unwrap()
+                    .unclaimed_payments,
+                bob_amount
+            );
+            assert_eq!(
+                contract
+                    .beneficiaries
+                    .get(accounts.charlie)
+                    .unwrap()
+                    .unclaimed_payments,
+                charlie_amount
+            );
+
+            // settling twice does not count the claims again
+            contract.settle_all_accrual().unwrap();
+            assert_eq!(contract.claims_in_period.total_claims, 2);
+
+            assert_eq!(contract.update_base_payment(2000), Ok(()));
+        }
+
+        /// Create a contract requiring it to be funded for one period
+        #[ink::test]
+        fn create_contract_require_funded() {
+            let accounts = default_accounts();
+            let beneficiaries = || {
+                vec![
+                    InitialBeneficiary {
+                        account_id: accounts.bob,
+                        multipliers: vec![(0, 100), (1, 3)],
+                    },
+                    InitialBeneficiary {
+                        account_id: accounts.charlie,
+                        multipliers: vec![(0, 100), (1, 3)],
+                    },
+                ]
+            };
+            let multipliers = || vec!["Seniority".to_string(), "Performance".to_string()];
+
+            // one period for both beneficiaries is 2060
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2059);
+            let res = OpenPayroll::new(
+                2,
+                1000,
+                multipliers(),
+                beneficiaries(),
+                true,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            );
+            assert!(matches!(res, Err(Error::NotEnoughBalanceInTreasury)));
+
+            // the check is opt-in
+            let res = OpenPayroll::new(
+                2,
+                1000,
+                multipliers(),
+                beneficiaries(),
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            );
+            assert!(res.is_ok());
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2060);
+            let res = OpenPayroll::new(
+                2,
+                1000,
+                multipliers(),
+                beneficiaries(),
+                true,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            );
+            assert!(res.is_ok());
+        }
+
+        /// Create a contract with the new_funded constructor
+        #[ink::test]
+        fn create_contract_new_funded() {
+            let accounts = default_accounts();
+            let beneficiaries = || {
+                vec![
+                    InitialBeneficiary {
+                        account_id: accounts.bob,
+                        multipliers: vec![(0, 100), (1, 3)],
+                    },
+                    InitialBeneficiary {
+                        account_id: accounts.charlie,
+                        multipliers: vec![(0, 100), (1, 3)],
+                    },
+                ]
+            };
+            let multipliers = || vec!["Seniority".to_string(), "Performance".to_string()];
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2059);
+            let res = OpenPayroll::new_funded(
+                2,
+                1000,
+                multipliers(),
+                beneficiaries(),
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            );
+            assert!(matches!(res, Err(Error::NotEnoughBalanceInTreasury)));
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2060);
+            let contract = OpenPayroll::new_funded(
+                2,
+                1000,
+                multipliers(),
+                beneficiaries(),
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            )
+            .expect("Cannot create contract");
+            assert!(contract.is_pre_funded());
+
+            let contract = OpenPayroll::new(
+                2,
+                1000,
+                multipliers(),
+                beneficiaries(),
+                true,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            )
+            .expect("Cannot create contract");
+            assert!(!contract.is_pre_funded());
+        }
+
+        /// Check if an account is a beneficiary
+        #[ink::test]
+        fn check_is_beneficiary() {
+            let (accounts, contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert!(contract.is_beneficiary(accounts.bob));
+            assert!(!contract.is_beneficiary(accounts.django));
+        }
+
+        /// Check the suggested periodicity for common durations
+        #[ink::test]
+        fn check_suggest_periodicity() {
+            let (_, contract) = create_accounts_and_contract(100_000_000u128);
+            let day = 86_400u64;
+
+            // 6 seconds blocks
+            assert_eq!(contract.suggest_periodicity(day, 6_000), 14_400);
+            assert_eq!(contract.suggest_periodicity(7 * day, 6_000), 100_800);
+            assert_eq!(contract.suggest_periodicity(30 * day, 6_000), 432_000);
+
+            // 12 seconds blocks
+            assert_eq!(contract.suggest_periodicity(7 * day, 12_000), 50_400);
+
+            // rounds to the closest block
+            assert_eq!(contract.suggest_periodicity(10, 4_000), 3);
+
+            // invalid block time and saturation
+            assert_eq!(contract.suggest_periodicity(day, 0), 0);
+            assert_eq!(contract.suggest_periodicity(u64::MAX, 1), u32::MAX);
+        }
+
+        /// Update the periodicity of a contract without beneficiaries after several periods
+        #[ink::test]
+        fn update_periodicity_with_no_beneficiaries() {
+            let accounts = default_accounts();
+            set_sender(accounts.alice);
+            let mut contract = create_contract_with_no_beneficiaries(100_000_000u128);
+
+            advance_n_blocks(7);
+
+            assert_eq!(contract.update_periodicity(10u32, false), Ok(()));
+            assert_eq!(contract.update_base_payment(2000), Ok(()));
+        }
+
+        /// Claim with an underfunded treasury fails without changing the beneficiary
+        #[ink::test]
+        fn claim_payment_with_insufficient_treasury() {
+            let (accounts, mut contract) = create_accounts_and_contract(1000u128);
+
+            // advance 3 blocks so a payment will be claimable
+            advance_n_blocks(3);
+
+            set_sender(accounts.bob);
+            let amount_to_claim = contract.get_amount_to_claim(accounts.bob).unwrap();
+            assert_eq!(
+                contract.claim_payment(accounts.bob, amount_to_claim, None),
+                Err(Error::NotEnoughBalanceInTreasury)
+            );
+
+            assert_eq!(
+                contract.get_amount_to_claim(accounts.bob),
+                Some(amount_to_claim)
+            );
+            assert_eq!(
+                contract.required_topup_for_next_period(),
+                3 * 1030 + 1030 - 1000
+            );
+        }
+
+        /// The cached multiplier sum follows updates, deactivations and deletions
+        #[ink::test]
+        fn check_cached_multiplier_sum() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            let cached_sum = |contract: &OpenPayroll, account_id: AccountId| {
+                contract
+                    .beneficiaries
+                    .get(account_id)
+                    .unwrap()
+                    .cached_multiplier_sum
+            };
+
+            assert_eq!(cached_sum(&contract, accounts.bob), Some(103));
+
+            contract
+                .update_beneficiary(accounts.bob, vec![(0, 100), (1, 20)])
+                .unwrap();
+            assert_eq!(cached_sum(&contract, accounts.bob), Some(120));
+            assert_eq!(cached_sum(&contract, accounts.charlie), Some(103));
+
+            // the accrual uses the cached sum while no deactivated multiplier applies
+            advance_n_blocks(2);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1200));
+
+            // the deactivated multiplier invalidates the cache until it is pruned
+            contract.deactivate_multiplier(1, false).unwrap();
+            assert_eq!(cached_sum(&contract, accounts.bob), None);
+            assert_eq!(cached_sum(&contract, accounts.charlie), None);
+            let bob = contract.beneficiaries.get(accounts.bob).unwrap();
+            assert_eq!(
+                contract._get_amount_to_claim_for_one_period(&bob, false),
+                1000
+            );
+            advance_n_blocks(2);
+
+            // everyone claims after the multiplier expires, then it can be deleted
+            advance_n_blocks(4);
+            for account_id in [accounts.bob, accounts.charlie] {
+                set_sender(account_id);
+                contract.claim_payment(account_id, 0, None).unwrap();
+            }
+            set_sender(accounts.alice);
+            assert_eq!(cached_sum(&contract, accounts.bob), Some(100));
+            contract.delete_unused_multiplier(1).unwrap();
+            assert_eq!(cached_sum(&contract, accounts.bob), Some(100));
+            assert_eq!(cached_sum(&contract, accounts.charlie), Some(100));
+
+            // the payout uses the cached sum
+            advance_n_blocks(2);
+            let bob = contract.beneficiaries.get(accounts.bob).unwrap();
+            assert_eq!(
+                contract._get_amount_to_claim_for_one_period(&bob, false),
+                1000
+            );
+        }
+
+        /// Prune the multipliers of a beneficiary after deleting a base multiplier
+        #[ink::test]
+        fn prune_beneficiary_multipliers() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            contract.deactivate_multiplier(1, false).unwrap();
+            advance_n_blocks(4);
+
+            // settling keeps the expired multiplier in the beneficiaries
+            contract.settle_all_accrual().unwrap();
+            contract.delete_unused_multiplier(1).unwrap();
+            assert!(contract
+                .beneficiaries
+                .get(accounts.bob)
+                .unwrap()
+                .multipliers
+                .contains_key(&1));
+
+            contract
+                .prune_beneficiary_multipliers(accounts.bob)
+                .unwrap();
+            assert_eq!(
+                contract
+                    .beneficiaries
+                    .get(accounts.bob)
+                    .unwrap()
+                    .multipliers,
+                vec_to_btreemap(&[(0, 100)])
+            );
+            match recorded_events().last() {
+                Some(Event::BeneficiaryPruned(BeneficiaryPruned {
+                    account_id,
+                    removed_ids,
+                })) => {
+                    assert_eq!(*account_id, accounts.bob);
+                    assert_eq!(*removed_ids, vec![1]);
+                }
+                _ => panic!("BeneficiaryPruned event not emitted"),
+            }
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                contract.prune_beneficiary_multipliers(accounts.charlie),
+                Err(Error::NotOwner)
+            );
+        }
+
+        /// The constructor emits the ContractInitialized event
+        #[ink::test]
+        fn check_contract_initialized_event() {
+            let (accounts, _) = create_accounts_and_contract(100_000_000u128);
+
+            let events = recorded_events();
+            match events.last() {
+                Some(Event::ContractInitialized(ContractInitialized {
+                    owner,
+                    base_payment,
+                    periodicity,
+                    initial_block,
+                    multiplier_count,
+                    beneficiary_count,
+                })) => {
+                    assert_eq!(*owner, accounts.alice);
+                    assert_eq!(*base_payment, 1000);
+                    assert_eq!(*periodicity, 2);
+                    assert_eq!(*initial_block, 0);
+                    assert_eq!(*multiplier_count, 2);
+                    assert_eq!(*beneficiary_count, 2);
+                }
+                _ => panic!("ContractInitialized event not emitted"),
+            }
+        }
+
+        /// Multiplier names are bounded by MAX_STRING_LEN
+        #[ink::test]
+        fn check_multiplier_name_length() {
+            let mut contract = create_contract_with_no_beneficiaries(100_000_000u128);
+
+            let at_limit = "a".repeat(MAX_STRING_LEN);
+            let over_limit = "a".repeat(MAX_STRING_LEN + 1);
+
+            assert_eq!(contract.add_base_multiplier(at_limit.clone()), Ok(()));
+            assert_eq!(
+                contract.add_base_multiplier(over_limit.clone()),
+                Err(Error::StringTooLong)
+            );
+
+            let res = OpenPayroll::new(
+                2,
+                1000,
+                vec![at_limit],
+                vec![],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            );
+            assert!(res.is_ok());
+            let res = OpenPayroll::new(
+                2,
+                1000,
+                vec![over_limit],
+                vec![],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            );
+            assert!(matches!(res, Err(Error::StringTooLong)));
+        }
+
+        /// Rollover the treasury to a new contract
+        #[ink::test]
+        fn rollover_to() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            let new_contract = AccountId::from([0x42; 32]);
+            set_balance(new_contract, 0);
+
+            advance_n_blocks(3);
+            let amount_to_claim = contract.get_amount_to_claim(accounts.bob).unwrap();
+
+            set_sender(accounts.bob);
+            assert_eq!(contract.rollover_to(new_contract), Err(Error::NotOwner));
+
+            set_sender(accounts.alice);
+            assert_eq!(
+                contract.rollover_to(contract_id()),
+                Err(Error::InvalidParams)
+            );
+            contract.rollover_to(new_contract).unwrap();
+
+            assert_eq!(contract.get_contract_balance(), 0);
+            assert_eq!(get_balance(new_contract), 100_000_000u128);
+            assert!(contract.is_closing());
+
+            // no new payments accrue
+            advance_n_blocks(6);
+            assert_eq!(
+                contract.get_amount_to_claim(accounts.bob),
+                Some(amount_to_claim)
+            );
+        }
+
+        /// Each accrued period is paid with the base payment in effect when it started
+        #[ink::test]
+        fn check_base_payment_history() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert_eq!(contract.get_base_payment_history(), vec![(0, 1000)]);
+
+            // in the initial period the base payment is replaced
+            contract.update_base_payment(1000).unwrap();
+            assert_eq!(contract.get_base_payment_history(), vec![(0, 1000)]);
+
+            // Bob is paid every 4 blocks, so his period in progress started before the change
+            contract
+                .set_beneficiary_periodicity(accounts.bob, Some(4))
+                .unwrap();
+            advance_n_blocks(6);
+            contract.claim_payment(accounts.bob, 0, None).unwrap();
+            contract.claim_payment(accounts.charlie, 0, None).unwrap();
+            assert_eq!(contract.get_last_updated(accounts.bob), Ok(4));
+
+            contract.update_base_payment(2000).unwrap();
+            assert_eq!(
+                contract.get_base_payment_history(),
+                vec![(0, 1000), (6, 2000)]
+            );
+
+            // Bob's period started at block 4 is paid with 1000, the one started at block 8 with 2000
+            advance_n_blocks(6);
+            assert_eq!(
+                contract.get_amount_to_claim(accounts.bob),
+                Some(1030 + 1030 + 2060)
+            );
+            assert_eq!(
+                contract.get_amount_to_claim(accounts.charlie),
+                Some(3 * 1030 + 3 * 2060)
+            );
+
+            // once every beneficiary accrues from block 12, the older base payments are dropped
+            contract.claim_payment(accounts.bob, 0, None).unwrap();
+            contract.claim_payment(accounts.charlie, 0, None).unwrap();
+            contract.update_base_payment(3000).unwrap();
+            assert_eq!(contract.get_base_payment_history(), vec![(12, 3000)]);
+
+            // the accrual can not be reset before the oldest base payment kept
+            assert_eq!(
+                contract.reset_accrual(accounts.charlie, 10),
+                Err(Error::InvalidParams)
+            );
+            assert!(contract.reset_accrual(accounts.charlie, 12).is_ok());
+        }
+
+        /// Emergency withdraw works on a paused contract only for the owner
+        #[ink::test]
+        fn emergency_withdraw() {
+            // the contract needs its own account to transfer to the owner
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(AccountId::from([0x07; 32]));
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            let owner_balance = get_balance(accounts.alice);
+
+            contract.pause(None).unwrap();
+
+            set_sender(accounts.bob);
+            assert_eq!(contract.emergency_withdraw(), Err(Error::NotOwner));
+
+            set_sender(accounts.alice);
+            contract.emergency_withdraw().unwrap();
+
+            assert_eq!(contract.get_contract_balance(), 0);
+            assert_eq!(get_balance(accounts.alice), owner_balance + 100_000_000u128);
+            assert!(contract.is_paused());
+            assert!(contract.is_closing());
+        }
+
+        /// The preview of the next period adds up to the total debt for the next period
+        #[ink::test]
+        fn check_preview_next_period() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            contract
+                .update_beneficiary(accounts.bob, vec![(0, 100), (1, 20)])
+                .unwrap();
+
+            // accrued periods are not included
+            advance_n_blocks(5);
+
+            let preview = contract.preview_next_period();
+            assert_eq!(
+                preview,
+                vec![(accounts.bob, 1200), (accounts.charlie, 1030)]
+            );
+            assert_eq!(
+                preview.iter().map(|(_, amount)| amount).sum::<Balance>(),
+                contract.get_total_debt_for_next_period()
+            );
+        }
+
+        /// The count of unclaimed beneficiaries does not overflow above 255 beneficiaries
+        #[ink::test]
+        fn check_count_of_unclaim_beneficiaries_above_u8() {
+            let mut contract = create_contract_with_no_beneficiaries(100_000_000u128);
+
+            // bypass MAX_BENEFICIARIES to store more than 255 beneficiaries
+            for number in 0..300u32 {
+                let mut arr_of_32 = [0u8; 32];
+                arr_of_32[..4].copy_from_slice(&number.to_be_bytes());
+                let account_id = AccountId::from(arr_of_32);
+                contract.beneficiaries.insert(
+                    account_id,
+                    &Beneficiary {
+                        account_id,
+                        multipliers: BTreeMap::new(),
+                        cached_multiplier_sum: Some(0),
+                        unclaimed_payments: 0,
+                        last_updated_period_block: 0,
+                        periodicity_override: None,
+                        frozen: false,
+                        added_at_block: 0,
+                        last_claim_period_block: 0,
+                    },
+                );
+                contract.beneficiaries_accounts.push(account_id);
+            }
+
+            advance_n_blocks(2);
+
+            assert_eq!(contract.get_count_of_unclaim_beneficiaries(), 300);
+            assert_eq!(contract.get_unclaimed_beneficiaries().len(), 300);
+        }
+
+        /// The zero address can not be proposed as owner
+        #[ink::test]
+        fn propose_zero_address_as_owner() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_001u128);
+
+            assert_eq!(
+                contract.propose_transfer_ownership(AccountId::from([0u8; 32]), 0),
+                Err(Error::InvalidParams)
+            );
+            assert_eq!(contract.proposed_owner, None);
+
+            assert_eq!(contract.propose_transfer_ownership(accounts.bob, 0), Ok(()));
+            assert_eq!(contract.proposed_owner, Some(accounts.bob));
+        }
+
+        /// The required top up brings an underfunded contract exactly to solvency
+        #[ink::test]
+        fn check_required_topup_for_next_period() {
+            let (_, contract) = create_accounts_and_contract(1000u128);
+
+            advance_n_blocks(2);
+
+            // one accrued period and the next one for both beneficiaries
+            let topup = contract.required_topup_for_next_period();
+            assert_eq!(topup, 4120 - 1000);
+
+            set_balance(contract_id(), 1000 + topup);
+            assert_eq!(
+                contract.get_contract_balance(),
+                contract.get_total_debt_with_unclaimed_for_next_period()
+            );
+            assert_eq!(contract.required_topup_for_next_period(), 0);
+
+            // nothing to deposit when already solvent
+            set_balance(contract_id(), 100_000_000u128);
+            assert_eq!(contract.required_topup_for_next_period(), 0);
+        }
+
+        /// Beneficiaries with different periodicities accrue at different rates
+        #[ink::test]
+        fn set_beneficiary_periodicity() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert_eq!(
+                contract.set_beneficiary_periodicity(accounts.bob, Some(0)),
+                Err(Error::InvalidParams)
+            );
+            assert_eq!(
+                contract.set_beneficiary_periodicity(accounts.django, Some(1)),
+                Err(Error::AccountNotFound)
+            );
+            contract
+                .set_beneficiary_periodicity(accounts.bob, Some(1))
+                .unwrap();
+
+            advance_n_blocks(4);
+
+            // bob is paid every block and charlie every 2 blocks
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(4 * 1030));
+            assert_eq!(
+                contract.get_amount_to_claim(accounts.charlie),
+                Some(2 * 1030)
+            );
+
+            // bob claims and accrues again from his own period
+            set_sender(accounts.bob);
+            contract
+                .claim_payment(accounts.bob, 4 * 1030, None)
+                .unwrap();
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(0));
+            advance_n_blocks(1);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                contract.set_beneficiary_periodicity(accounts.bob, None),
+                Err(Error::NotOwner)
+            );
+        }
+
+        /// A periodicity override never moves the last payment block before the settled one
+        #[ink::test]
+        fn set_beneficiary_periodicity_keeps_accrual() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(7);
+            assert_eq!(contract.settle_accrual(accounts.bob), Ok(3 * 1030));
+            assert_eq!(contract.get_last_updated(accounts.bob), Ok(6));
+
+            // the period of 4 blocks started at block 4 overlaps the settled block 6
+            contract
+                .set_beneficiary_periodicity(accounts.bob, Some(4))
+                .unwrap();
+            assert_eq!(contract.get_last_updated(accounts.bob), Ok(8));
+
+            advance_n_blocks(1);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(3 * 1030));
+            advance_n_blocks(4);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(4 * 1030));
+        }
+
+        /// Check the role of the caller
+        #[ink::test]
+        fn check_my_role() {
+            let (accounts, contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert_eq!(contract.my_role(), Role::Owner);
+
+            set_sender(accounts.bob);
+            assert_eq!(contract.my_role(), Role::Beneficiary);
+
+            set_sender(accounts.django);
+            assert_eq!(contract.my_role(), Role::None);
+        }
+
+        /// Check a zero amount claim settles accrual while the treasury is short
+        #[ink::test]
+        fn check_claim_zero_with_short_treasury() {
+            let (accounts, mut contract) = create_accounts_and_contract(100u128);
+
+            advance_n_blocks(2);
+
+            set_sender(accounts.bob);
+            assert!(matches!(
+                contract.claim_payment(accounts.bob, 1030, None),
+                Err(Error::NotEnoughBalanceInTreasury)
+            ));
+            assert!(contract.claim_payment(accounts.bob, 0, None).is_ok());
+
+            let beneficiary = contract.get_beneficiary(accounts.bob).unwrap();
+            assert_eq!(beneficiary.unclaimed_payments, 1030);
+            assert_eq!(
+                beneficiary.last_updated_period_block,
+                contract.get_current_period_initial_block()
+            );
+        }
+
+        /// A frozen beneficiary keeps accruing but can not claim until unfrozen
+        #[ink::test]
+        fn check_beneficiary_frozen() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            contract.set_beneficiary_frozen(accounts.bob, true).unwrap();
+            advance_n_blocks(4);
+
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(2060));
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 1030, None),
+                Err(Error::BeneficiaryFrozen)
+            );
+            assert_eq!(
+                contract.set_beneficiary_frozen(accounts.bob, false),
+                Err(Error::NotOwner)
+            );
+
+            set_sender(accounts.alice);
+            contract
+                .set_beneficiary_frozen(accounts.bob, false)
+                .unwrap();
+
+            set_sender(accounts.bob);
+            assert!(contract.claim_payment(accounts.bob, 2060, None).is_ok());
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(0));
+        }
+
+        /// Check the number of periods elapsed since the contract start
+        #[ink::test]
+        fn check_periods_elapsed() {
+            let (_, contract) = create_accounts_and_contract(100_000_000u128);
+            assert_eq!(contract.periods_elapsed(), 0);
+
+            advance_n_blocks(1);
+            assert_eq!(contract.periods_elapsed(), 0);
+
+            advance_n_blocks(6);
+            assert_eq!(contract.periods_elapsed(), 3);
+        }
+
+        /// The debts stop counting a multiplier once it is deactivated, so deleting it keeps the freed headroom
+        #[ink::test]
+        fn check_balance_with_debts_after_deleting_multiplier() {
+            let (_, mut contract) = create_accounts_and_contract(100_000_000u128);
+            assert_eq!(contract.get_total_debt_for_next_period(), 2060);
+
+            contract.deactivate_multiplier(1, false).unwrap();
+            assert_eq!(contract.get_total_debt_for_next_period(), 2000);
+
+            advance_n_blocks(3);
+            contract.settle_all_accrual().unwrap();
+            let balance_with_debts = contract.get_balance_with_debts();
+
+            contract.delete_unused_multiplier(1).unwrap();
+            assert_eq!(contract.get_balance_with_debts(), balance_with_debts);
+            assert_eq!(contract.get_total_debt_for_next_period(), 2000);
+        }
+
+        /// Check the beneficiary view matches the individual getters
+        #[ink::test]
+        fn check_beneficiary_view() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(3);
+
+            let view = contract.get_beneficiary_view(accounts.bob).unwrap();
+            assert_eq!(
+                Some(view.beneficiary),
+                contract.get_beneficiary(accounts.bob)
+            );
+            assert_eq!(
+                Some(view.amount_to_claim),
+                contract.get_amount_to_claim(accounts.bob)
+            );
+            assert_eq!(view.next_payout_block, contract.get_next_block_period());
+            assert!(view.can_claim);
+
+            contract.pause(None).unwrap();
+            assert!(
+                !contract
+                    .get_beneficiary_view(accounts.bob)
+                    .unwrap()
+                    .can_claim
+            );
+
+            assert_eq!(
+                contract.get_beneficiary_view(accounts.django),
+                Err(Error::AccountNotFound)
+            );
+        }
+
+        /// Multiplier values above the max ratio of the multiplier base are rejected
+        #[ink::test]
+        fn check_max_multiplier_ratio() {
+            let accounts = default_accounts();
+            let beneficiary = |value| InitialBeneficiary {
+                account_id: accounts.bob,
+                multipliers: vec![(0, value)],
+            };
+            let multipliers = || vec!["Seniority".to_string()];
+
+            let res = OpenPayroll::new(
+                2,
+                1000,
+                multipliers(),
+                vec![beneficiary(301)],
+                false,
+                Some(3),
+                EmptyPolicy::PayFraction,
+                false,
+            );
+            assert!(matches!(res, Err(Error::MultiplierValueTooLarge)));
+
+            // the check is disabled by default
+            let res = OpenPayroll::new(
+                2,
+                1000,
+                multipliers(),
+                vec![beneficiary(301)],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            );
+            assert!(res.is_ok());
+
+            let mut contract = OpenPayroll::new(
+                2,
+                1000,
+                multipliers(),
+                vec![beneficiary(300)],
+                false,
+                Some(3),
+                EmptyPolicy::PayFraction,
+                false,
+            )
+            .expect("Cannot create contract");
+
+            assert_eq!(
+                contract.add_beneficiary(accounts.charlie, vec![(0, 100_000)]),
+                Err(Error::MultiplierValueTooLarge)
+            );
+            assert_eq!(
+                contract.update_beneficiary(accounts.bob, vec![(0, 301)]),
+                Err(Error::MultiplierValueTooLarge)
+            );
+            assert!(contract
+                .add_beneficiary(accounts.charlie, vec![(0, 300)])
+                .is_ok());
+        }
+
+        /// The block in which a beneficiary is added is stored
+        #[ink::test]
+        fn check_added_at_block() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(5);
+            contract
+                .add_beneficiary(accounts.django, vec![(0, 100), (1, 3)])
+                .unwrap();
+
+            let beneficiary = contract.get_beneficiary(accounts.django).unwrap();
+            assert_eq!(beneficiary.added_at_block, 5);
+            assert_eq!(
+                contract
+                    .get_beneficiary(accounts.bob)
+                    .unwrap()
+                    .added_at_block,
+                0
+            );
+        }
+
+        /// Check the total multiplier weight of the beneficiaries
+        #[ink::test]
+        fn check_total_multiplier_weight() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            assert_eq!(contract.total_multiplier_weight(), 206);
+
+            contract.remove_beneficiary(accounts.charlie).unwrap();
+            assert_eq!(contract.total_multiplier_weight(), 103);
+
+            // A beneficiary without multipliers adds nothing, whatever the empty multiplier policy
+            contract.add_beneficiary(accounts.django, vec![]).unwrap();
+            assert_eq!(contract.total_multiplier_weight(), 103);
+
+            // An overflowing sum saturates
+            contract.remove_beneficiary(accounts.django).unwrap();
+            contract
+                .add_beneficiary(accounts.django, vec![(0, u128::MAX)])
+                .unwrap();
+            assert_eq!(contract.total_multiplier_weight(), u128::MAX);
+        }
+
+        /// A claim can not take the unclaimed payments committed to other beneficiaries
+        #[ink::test]
+        fn claim_payment_would_strand_committed_payments() {
+            let (accounts, mut contract) = create_accounts_and_contract(2000u128);
+
+            advance_n_blocks(2);
+
+            // charlie commits the payment of the period
+            set_sender(accounts.charlie);
+            contract.claim_payment(accounts.charlie, 0, None).unwrap();
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 1030, None),
+                Err(Error::WouldStrandCommittedPayments)
+            );
+            assert!(contract.claim_payment(accounts.bob, 970, None).is_ok());
+
+            // the 60 left by bob are now committed as well
+            set_sender(accounts.charlie);
+            assert_eq!(
+                contract.claim_payment(accounts.charlie, 1030, None),
+                Err(Error::WouldStrandCommittedPayments)
+            );
+            assert!(contract.claim_payment(accounts.charlie, 970, None).is_ok());
+        }
+
+        /// Pause with a reason and clear it on resume
+        #[ink::test]
+        fn check_pause_reason() {
+            let (_, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert_eq!(
+                contract.pause(Some("a".repeat(MAX_STRING_LEN + 1))),
+                Err(Error::StringTooLong)
+            );
+            assert!(!contract.is_paused());
+
+            contract.pause(Some("Treasury audit".to_string())).unwrap();
+            assert_eq!(
+                contract.get_pause_reason(),
+                Some("Treasury audit".to_string())
+            );
+            match recorded_events().last() {
+                Some(Event::Paused(Paused { reason })) => {
+                    assert_eq!(*reason, Some("Treasury audit".to_string()));
+                }
+                _ => panic!("Paused event not emitted"),
+            }
+
+            contract.resume().unwrap();
+            assert_eq!(contract.get_pause_reason(), None);
+        }
+
+        /// The accrual cursor moves to the current period start after a claim
+        #[ink::test]
+        fn check_last_updated() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            assert_eq!(contract.get_last_updated(accounts.bob), Ok(0));
+
+            advance_n_blocks(5);
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 1030, None).unwrap();
+
+            assert_eq!(contract.get_last_updated(accounts.bob), Ok(4));
+            assert_eq!(
+                contract.get_last_updated(accounts.django),
+                Err(Error::AccountNotFound)
+            );
+        }
+
+        /// Pay every beneficiary their final amount once the contract is closing
+        #[ink::test]
+        fn check_final_distribute() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            let new_contract = AccountId::from([0x42; 32]);
+            set_balance(new_contract, 0);
+
+            advance_n_blocks(4);
+            assert_eq!(contract.final_distribute(), Err(Error::ContractNotClosing));
+
+            contract.rollover_to(new_contract).unwrap();
+
+            // the treasury is topped up again, but not enough for both beneficiaries
+            set_balance(contract_id(), 4119);
+            assert_eq!(
+                contract.final_distribute(),
+                Err(Error::NotEnoughBalanceInTreasury)
+            );
+
+            set_balance(contract_id(), 4120);
+            set_sender(accounts.bob);
+            assert_eq!(contract.final_distribute(), Err(Error::NotOwner));
+
+            set_sender(accounts.alice);
+            assert_eq!(contract.final_distribute(), Ok(2));
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(0));
+            assert_eq!(contract.get_amount_to_claim(accounts.charlie), Some(0));
+            assert_eq!(contract.get_contract_balance(), 0);
+
+            // nothing is left to distribute
+            assert_eq!(contract.final_distribute(), Ok(0));
+        }
+
+        /// The Claimed event carries the treasury balance after the claim
+        #[ink::test]
+        fn check_claimed_event_treasury_after() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(3);
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 1000, None).unwrap();
+
+            match recorded_events().last() {
+                Some(Event::Claimed(Claimed { treasury_after, .. })) => {
+                    assert_eq!(*treasury_after, contract.get_contract_balance());
+                    assert_eq!(*treasury_after, 100_000_000 - 1000);
+                }
+                _ => panic!("Claimed event not emitted"),
+            }
+        }
+
+        /// The empty multiplier policy sets the payment of beneficiaries without multipliers
+        #[ink::test]
+        fn check_empty_multiplier_policy() {
+            let accounts = default_accounts();
+            set_balance(contract_id(), 100_000_000u128);
+
+            for (policy, expected) in [
+                (EmptyPolicy::PayFraction, 10),
+                (EmptyPolicy::PayNothing, 0),
+                (EmptyPolicy::PayFullBase, 1000),
+            ] {
+                let beneficiary = InitialBeneficiary {
+                    account_id: accounts.bob,
+                    multipliers: vec![],
+                };
+                let contract = OpenPayroll::new(
+                    2,
+                    1000,
+                    vec![],
+                    vec![beneficiary],
+                    false,
+                    None,
+                    policy,
+                    false,
+                )
+                .expect("Cannot create contract");
+
+                advance_n_blocks(2);
+                assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(expected));
+            }
+        }
+
+        /// Get the amounts to claim of known and unknown accounts
+        #[ink::test]
+        fn check_get_amounts_to_claim() {
+            let (accounts, contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(2);
+
+            assert_eq!(
+                contract.get_amounts_to_claim(vec![
+                    accounts.bob,
+                    accounts.django,
+                    accounts.charlie
+                ]),
+                vec![Ok(1030), Err(Error::AccountNotFound), Ok(1030)]
+            );
+        }
+
+        /// No accrual is lost or paid twice when the periodicity changes mid period
+        #[ink::test]
+        fn update_periodicity_keeps_accrual() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(5);
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 0, None).unwrap();
+            set_sender(accounts.charlie);
+            contract.claim_payment(accounts.charlie, 0, None).unwrap();
+
+            set_sender(accounts.alice);
+            contract.update_periodicity(3, false).unwrap();
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(2060));
+
+            // the period started at block 3 overlaps the settled block 4, so accrual starts at block 6
+            assert_eq!(contract.get_last_updated(accounts.bob), Ok(6));
+            advance_n_blocks(1);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(2060));
+
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 2060, None).unwrap();
+
+            advance_n_blocks(2);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(0));
+            advance_n_blocks(1);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+        }
+
+        /// Check the account id of the contract
+        #[ink::test]
+        fn check_contract_account() {
+            let (_, contract) = create_accounts_and_contract(100_000_000u128);
+            assert_eq!(contract.get_contract_account(), contract_id());
+        }
+
+        /// Check the beneficiaries that are not up to date
+        #[ink::test]
+        fn check_overdue_beneficiaries() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            assert_eq!(contract.get_overdue_beneficiaries(), vec![]);
+
+            advance_n_blocks(3);
+            assert_eq!(
+                contract.get_overdue_beneficiaries(),
+                vec![accounts.bob, accounts.charlie]
+            );
+
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 0, None).unwrap();
+            assert_eq!(contract.get_overdue_beneficiaries(), vec![accounts.charlie]);
+            assert_eq!(
+                contract.ensure_all_payments_uptodate(),
+                Err(Error::PaymentsNotUpToDate)
+            );
+        }
+
+        /// The ownership can only be accepted up to the accept by block
+        #[ink::test]
+        fn accept_ownership_expired() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            contract
+                .propose_transfer_ownership(accounts.bob, 2)
+                .unwrap();
+            advance_n_blocks(3);
+
+            set_sender(accounts.bob);
+            assert_eq!(contract.accept_ownership(), Err(Error::ProposalExpired));
+            assert_eq!(contract.owner, accounts.alice);
+
+            set_sender(accounts.alice);
+            contract
+                .propose_transfer_ownership(accounts.bob, 5)
+                .unwrap();
+            advance_n_blocks(2);
+
+            set_sender(accounts.bob);
+            assert_eq!(contract.accept_ownership(), Ok(()));
+            assert_eq!(contract.owner, accounts.bob);
+        }
+
+        /// The memo of a claim is emitted in the Claimed event
+        #[ink::test]
+        fn claim_payment_with_memo() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(3);
+            set_sender(accounts.bob);
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 1030, Some("a".repeat(MAX_STRING_LEN + 1))),
+                Err(Error::StringTooLong)
+            );
+            contract
+                .claim_payment(accounts.bob, 1030, Some("INV-2024-001".to_string()))
+                .unwrap();
+
+            match recorded_events().last() {
+                Some(Event::Claimed(Claimed { memo, .. })) => {
+                    assert_eq!(*memo, Some("INV-2024-001".to_string()));
+                }
+                _ => panic!("Claimed event not emitted"),
+            }
+        }
+
+        /// The multipliers of a beneficiary are returned sorted by id
+        #[ink::test]
+        fn check_get_beneficiary_multipliers() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            contract
+                .update_beneficiary(accounts.bob, vec![(1, 20), (0, 100)])
+                .unwrap();
+
+            assert_eq!(
+                contract.get_beneficiary_multipliers(accounts.bob),
+                Ok(vec![(0, 100), (1, 20)])
+            );
+            assert_eq!(
+                contract.get_beneficiary_multipliers(accounts.django),
+                Err(Error::AccountNotFound)
+            );
+        }
+
+        /// The last active multiplier can only be deactivated if the contract does not require one
+        #[ink::test]
+        fn check_require_min_one_multiplier() {
+            let accounts = default_accounts();
+            set_balance(contract_id(), 100_000_000u128);
+            let multipliers = || vec!["Seniority".to_string(), "Performance".to_string()];
+            let beneficiaries = || {
+                vec![InitialBeneficiary {
+                    account_id: accounts.bob,
+                    multipliers: vec![(0, 100), (1, 3)],
+                }]
+            };
+
+            let mut contract = OpenPayroll::new(
+                2,
+                1000,
+                multipliers(),
+                beneficiaries(),
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                true,
+            )
+            .expect("Cannot create contract");
+            contract.deactivate_multiplier(0, false).unwrap();
+            assert_eq!(
+                contract.deactivate_multiplier(1, false),
+                Err(Error::WouldLeaveNoMultipliers)
+            );
+
+            let mut contract = OpenPayroll::new(
+                2,
+                1000,
+                multipliers(),
+                beneficiaries(),
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            )
+            .expect("Cannot create contract");
+            contract.deactivate_multiplier(0, false).unwrap();
+            assert_eq!(contract.deactivate_multiplier(1, false), Ok(()));
+        }
+
+        /// Update several beneficiaries at once
+        #[ink::test]
+        fn update_beneficiaries() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            contract
+                .update_beneficiaries(vec![
+                    (accounts.bob, vec![(0, 200), (1, 20)]),
+                    (accounts.charlie, vec![(0, 150)]),
+                ])
+                .unwrap();
+            assert_eq!(
+                contract.get_beneficiary_multipliers(accounts.bob),
+                Ok(vec![(0, 200), (1, 20)])
+            );
+            assert_eq!(
+                contract.get_beneficiary_multipliers(accounts.charlie),
+                Ok(vec![(0, 150)])
+            );
+            let updated_events = recorded_events()
+                .iter()
+                .filter(|event| matches!(event, Event::BeneficiaryUpdated(_)))
+                .count();
+            assert_eq!(updated_events, 2);
+        }
+
+        /// A batch with an invalid entry leaves every beneficiary untouched
+        #[ink::test]
+        fn update_beneficiaries_with_unknown_account() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert_eq!(
+                contract.update_beneficiaries(vec![
+                    (accounts.bob, vec![(0, 200), (1, 20)]),
+                    (accounts.django, vec![(0, 150)]),
+                ]),
+                Err(Error::AccountNotFound)
+            );
+            assert_eq!(
+                contract.get_beneficiary_multipliers(accounts.bob),
+                Ok(vec![(0, 100), (1, 3)])
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(contract.update_beneficiaries(vec![]), Err(Error::NotOwner));
+        }
+
+        /// Check the claims made in the current period
+        #[ink::test]
+        fn check_claims_status() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(3);
+            assert_eq!(contract.claims_status(), (2, 0, 2));
+
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 0, None).unwrap();
+            assert_eq!(contract.claims_status(), (2, 1, 2));
+
+            set_sender(accounts.charlie);
+            contract.claim_payment(accounts.charlie, 0, None).unwrap();
+            assert_eq!(contract.claims_status(), (2, 2, 2));
+
+            advance_n_blocks(1);
+            assert_eq!(contract.claims_status(), (4, 0, 2));
+        }
+
+        /// Reset the accrual of a beneficiary to a later period
+        #[ink::test]
+        fn reset_accrual() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(5);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(2060));
+
+            contract.reset_accrual(accounts.bob, 2).unwrap();
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+            assert_eq!(contract.get_last_updated(accounts.bob), Ok(2));
+
+            // not a period start
+            assert_eq!(
+                contract.reset_accrual(accounts.bob, 3),
+                Err(Error::InvalidParams)
+            );
+            // in the future
+            assert_eq!(
+                contract.reset_accrual(accounts.bob, 6),
+                Err(Error::InvalidParams)
+            );
+            assert_eq!(
+                contract.reset_accrual(accounts.django, 2),
+                Err(Error::AccountNotFound)
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                contract.reset_accrual(accounts.bob, 4),
+                Err(Error::NotOwner)
+            );
+        }
+
+        /// The total debts saturate instead of overflowing
+        #[ink::test]
+        fn check_total_debts_saturate() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            for account_id in [accounts.bob, accounts.charlie] {
+                let mut beneficiary = contract.beneficiaries.get(account_id).unwrap();
+                beneficiary.unclaimed_payments = u128::MAX / 2 + 1;
+                contract.beneficiaries.insert(account_id, &beneficiary);
+            }
+
+            assert_eq!(contract.get_total_debts(), u128::MAX);
+            assert_eq!(
+                contract.get_total_debt_with_unclaimed_for_next_period(),
+                u128::MAX
+            );
+            assert_eq!(contract.get_total_debt_for_next_period(), 2060);
+            assert_eq!(contract.get_balance_with_debts(), 0);
+        }
+
+        /// The accrual saturates instead of overflowing with a huge base payment
+        #[ink::test]
+        fn check_accrual_saturates() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            contract.update_base_payment(u128::MAX / 50).unwrap();
+
+            advance_n_blocks(4);
+            assert_eq!(
+                contract.get_amount_to_claim(accounts.bob),
+                Some(u128::MAX / 100 * 2)
+            );
+            assert_eq!(contract.get_balance_with_debts(), 0);
+
+            advance_n_blocks(200);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(u128::MAX));
+            assert_eq!(contract.get_total_debts(), u128::MAX);
+            assert_eq!(contract.get_balance_with_debts(), 0);
+        }
+
+        /// Report the beneficiaries whose multipliers do not match the base multipliers
+        #[ink::test]
+        fn check_find_inconsistent_beneficiaries() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            assert_eq!(contract.find_inconsistent_beneficiaries(), vec![]);
+
+            // deactivated multipliers are not an inconsistency
+            contract.deactivate_multiplier(1, false).unwrap();
+            assert_eq!(contract.find_inconsistent_beneficiaries(), vec![]);
+
+            contract
+                .add_base_multiplier("Experience".to_string())
+                .unwrap();
+            contract
+                .update_beneficiary(accounts.bob, vec![(0, 100), (2, 5)])
+                .unwrap();
+            assert_eq!(
+                contract.find_inconsistent_beneficiaries(),
+                vec![accounts.charlie]
+            );
+        }
+
+        /// Create a contract giving the multipliers of the beneficiaries by name
+        #[ink::test]
+        fn create_contract_with_named_multipliers() {
+            let accounts = default_accounts();
+            set_balance(contract_id(), 100_000_000u128);
+            let multipliers = || vec!["Seniority".to_string(), "Performance".to_string()];
+            let beneficiary = |name: &str| NamedInitialBeneficiary {
+                account_id: accounts.bob,
+                multipliers: vec![(name.to_string(), 3), ("Seniority".to_string(), 100)],
+            };
+
+            let mut contract = OpenPayroll::new_with_named_multipliers(
+                2,
+                1000,
+                multipliers(),
+                vec![beneficiary("Performance")],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            )
+            .expect("Cannot create contract");
+            assert_eq!(
+                contract.get_beneficiary(accounts.bob).unwrap().multipliers,
+                vec_to_btreemap(&[(0, 100), (1, 3)])
+            );
+
+            let res = OpenPayroll::new_with_named_multipliers(
+                2,
+                1000,
+                multipliers(),
+                vec![beneficiary("Experience")],
+                false,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            );
+            assert!(matches!(res, Err(Error::MultiplierNotFound)));
+        }
+
+        /// A deactivated multiplier can be deleted after the next period boundary
+        #[ink::test]
+        fn check_deletable_at() {
+            let (_, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert_eq!(
+                contract.get_deletable_at(1),
+                Err(Error::MultiplierNotDeactivated)
+            );
+            assert_eq!(contract.get_deletable_at(7), Err(Error::MultiplierNotFound));
+
+            advance_n_blocks(3);
+            contract.deactivate_multiplier(1, false).unwrap();
+            assert_eq!(
+                contract.get_deletable_at(1),
+                Ok(contract.get_next_block_period())
+            );
+            assert_eq!(contract.get_deletable_at(1), Ok(4));
+        }
+
+        /// The schedule can only be updated once every beneficiary claimed in the period
+        #[ink::test]
+        fn check_can_update_schedule() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(3);
+            assert!(!contract.can_update_schedule());
+
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 0, None).unwrap();
+            assert!(!contract.can_update_schedule());
+
+            set_sender(accounts.charlie);
+            contract.claim_payment(accounts.charlie, 0, None).unwrap();
+            assert!(contract.can_update_schedule());
+
+            set_sender(accounts.alice);
+            assert_eq!(contract.update_base_payment(2000), Ok(()));
+        }
+
+        /// Freezing and unfreezing a beneficiary emits BeneficiaryFreezeChanged
+        #[ink::test]
+        fn check_beneficiary_freeze_changed_event() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            for expected in [true, false] {
+                contract
+                    .set_beneficiary_frozen(accounts.bob, expected)
+                    .unwrap();
+                match recorded_events().last() {
+                    Some(Event::BeneficiaryFreezeChanged(BeneficiaryFreezeChanged {
+                        account_id,
+                        frozen,
+                    })) => {
+                        assert_eq!(*account_id, accounts.bob);
+                        assert_eq!(*frozen, expected);
+                    }
+                    _ => panic!("BeneficiaryFreezeChanged event not emitted"),
+                }
+            }
+        }
+
+        /// Check the amount the caller can claim
+        #[ink::test]
+        fn check_my_claimable() {
+            let (accounts, contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(3);
+
+            set_sender(accounts.bob);
+            assert_eq!(contract.my_claimable(), 1030);
+
+            set_sender(accounts.django);
+            assert_eq!(contract.my_claimable(), 0);
+        }
+
+        /// A multiplier deactivated for the next period still counts in the period in progress
+        #[ink::test]
+        fn deactivate_multiplier_next_period() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(3);
+            contract.deactivate_multiplier(1, false).unwrap();
+            assert_eq!(contract.get_deletable_at(1), Ok(4));
+
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 1030, None).unwrap();
+
+            // the period from block 2 to 4 is paid with the multiplier
+            advance_n_blocks(2);
+            assert!(contract.claim_payment(accounts.bob, 1030, None).is_ok());
+        }
+
+        /// A multiplier deactivated immediately no longer counts in the period in progress
+        #[ink::test]
+        fn deactivate_multiplier_immediate() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(3);
+            contract.deactivate_multiplier(1, true).unwrap();
+            assert_eq!(contract.get_deletable_at(1), Ok(2));
+
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 1030, None).unwrap();
+
+            // the period from block 2 to 4 is paid without the multiplier
+            advance_n_blocks(2);
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 1030, None),
+                Err(Error::ClaimedAmountIsBiggerThanAvailable)
+            );
+            assert!(contract.claim_payment(accounts.bob, 1000, None).is_ok());
+        }
+
+        /// A claim after an immediate deactivation pays the period in progress without the multiplier
+        #[ink::test]
+        fn claim_payment_after_deactivate_multiplier_immediate() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(3);
+            contract.deactivate_multiplier(1, true).unwrap();
+
+            // the period from block 0 to 2 is paid with the multiplier, the one from 2 to 4 without it
+            advance_n_blocks(2);
+            assert_eq!(
+                contract.get_amount_to_claim(accounts.bob),
+                Some(1030 + 1000)
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 1030 + 1000 + 1, None),
+                Err(Error::ClaimedAmountIsBiggerThanAvailable)
+            );
+            assert!(contract
+                .claim_payment(accounts.bob, 1030 + 1000, None)
+                .is_ok());
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(0));
+        }
+
+        /// A beneficiary with a longer periodicity can claim after a multiplier it still holds is deleted
+        #[ink::test]
+        fn claim_payment_after_delete_multiplier_with_periodicity_override() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            contract
+                .set_beneficiary_periodicity(accounts.bob, Some(6))
+                .unwrap();
+            contract.deactivate_multiplier(1, false).unwrap();
+
+            // Bob is still in the period starting at block 0, so he keeps the multiplier
+            advance_n_blocks(3);
+            for account_id in [accounts.bob, accounts.charlie] {
+                contract.claim_payment(account_id, 0, None).unwrap();
+            }
+            assert!(contract
+                .beneficiaries
+                .get(accounts.bob)
+                .unwrap()
+                .multipliers
+                .contains_key(&1));
+            contract.delete_unused_multiplier(1).unwrap();
+
+            // the deleted multiplier no longer counts in the period
+            advance_n_blocks(3);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1000));
+            assert!(contract.claim_payment(accounts.bob, 1000, None).is_ok());
+            assert!(!contract
+                .beneficiaries
+                .get(accounts.bob)
+                .unwrap()
+                .multipliers
+                .contains_key(&1));
+        }
+
+        /// The period index increments at each period boundary
+        #[ink::test]
+        fn check_period_index_at() {
+            let (_, contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert_eq!(contract.period_index_at(0), 0);
+            assert_eq!(contract.period_index_at(1), 0);
+            assert_eq!(contract.period_index_at(2), 1);
+            assert_eq!(contract.period_index_at(3), 1);
+            assert_eq!(contract.period_index_at(4), 2);
+        }
+
+        /// Nothing accrues while the beneficiary starts in the future
+        #[ink::test]
+        fn check_future_start_beneficiary() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            let mut beneficiary = contract.beneficiaries.get(accounts.bob).unwrap();
+            beneficiary.last_updated_period_block = 6;
+            contract.beneficiaries.insert(accounts.bob, &beneficiary);
+
+            advance_n_blocks(3);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(0));
+
+            advance_n_blocks(5);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+        }
+
+        /// A partial claim leaves stored unclaimed payments
+        #[ink::test]
+        fn check_beneficiaries_with_unclaimed() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(3);
+            assert_eq!(contract.get_beneficiaries_with_unclaimed(), vec![]);
+
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 1000, None).unwrap();
+            assert_eq!(
+                contract.get_beneficiaries_with_unclaimed(),
+                vec![(accounts.bob, 30)]
+            );
+        }
+
+        /// Grant bonuses to several beneficiaries at once
+        #[ink::test]
+        fn grant_bonuses() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            contract
+                .grant_bonuses(vec![(accounts.bob, 500), (accounts.charlie, 200)])
+                .unwrap();
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(500));
+            assert_eq!(contract.get_amount_to_claim(accounts.charlie), Some(200));
+
+            let bonus_events = recorded_events()
+                .iter()
+                .filter(|event| matches!(event, Event::BonusGranted(_)))
+                .count();
+            assert_eq!(bonus_events, 2);
+
+            set_sender(accounts.bob);
+            assert_eq!(contract.grant_bonuses(vec![]), Err(Error::NotOwner));
+        }
+
+        /// A batch of bonuses with an unknown account leaves every beneficiary untouched
+        #[ink::test]
+        fn grant_bonuses_with_unknown_account() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert_eq!(
+                contract.grant_bonuses(vec![(accounts.bob, 500), (accounts.django, 200)]),
+                Err(Error::AccountNotFound)
+            );
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(0));
+
+            assert_eq!(
+                contract.grant_bonuses(vec![(accounts.bob, 500), (accounts.charlie, u128::MAX)]),
+                Ok(())
+            );
+            assert_eq!(
+                contract.grant_bonuses(vec![(accounts.bob, 500), (accounts.charlie, 1)]),
+                Err(Error::AmountOverflow)
+            );
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(500));
+        }
+
+        /// The accrual stops growing once the maximum unclaimed periods are reached
+        #[ink::test]
+        fn check_max_unclaimed_periods() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert_eq!(
+                contract.set_max_unclaimed_periods(Some(0)),
+                Err(Error::InvalidParams)
+            );
+            contract.set_max_unclaimed_periods(Some(2)).unwrap();
+
+            advance_n_blocks(4);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(2060));
+            advance_n_blocks(6);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(2060));
+
+            // once claimed it accrues again
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 2060, None).unwrap();
+            advance_n_blocks(2);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+
+            assert_eq!(
+                contract.set_max_unclaimed_periods(None),
+                Err(Error::NotOwner)
+            );
+        }
+
+        /// Only one claim per period is allowed when the contract requires it
+        #[ink::test]
+        fn check_one_claim_per_period() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            contract.set_one_claim_per_period(true).unwrap();
+
+            advance_n_blocks(3);
+            set_sender(accounts.bob);
+            assert!(contract.claim_payment(accounts.bob, 1000, None).is_ok());
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 30, None),
+                Err(Error::AlreadyClaimedThisPeriod)
+            );
+            assert!(contract.claim_payment(accounts.bob, 0, None).is_ok());
+
+            // the next period can be claimed again
+            advance_n_blocks(2);
+            assert!(contract.claim_payment(accounts.bob, 30, None).is_ok());
+
+            assert_eq!(
+                contract.set_one_claim_per_period(false),
+                Err(Error::NotOwner)
+            );
+        }
+
+        /// Check the solvency ratio in basis points
+        #[ink::test]
+        fn check_solvency_bps() {
+            let (_, contract) = create_accounts_and_contract(2472u128);
+            assert_eq!(contract.solvency_bps(), u32::MAX);
+
+            // 2 beneficiaries * 1030 = 2060 of debts
+            advance_n_blocks(2);
+            assert_eq!(contract.solvency_bps(), 12_000);
+        }
+
+        /// Check the counters of the contract
+        #[ink::test]
+        fn check_stats() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            contract
+                .add_base_multiplier("Experience".to_string())
+                .unwrap();
+            advance_n_blocks(3);
+            set_sender(accounts.bob);
+            contract.claim_payment(accounts.bob, 1000, None).unwrap();
+
+            let stats = contract.get_stats();
+            assert_eq!(stats.total_paid, 1000);
+            assert_eq!(
+                stats.beneficiary_count,
+                contract.get_list_beneficiaries().len() as u32
+            );
+            assert_eq!(
+                stats.multiplier_count,
+                contract.get_multipliers_list().len() as u32
+            );
+            assert_eq!(stats.next_multiplier_id, 3);
+            assert_eq!(stats.claims_this_period, contract.claims_status().1);
+            assert_eq!(stats.claims_this_period, 1);
+        }
+
+        /// The zero address can not accept the ownership
+        #[ink::test]
+        fn accept_ownership_by_zero_address() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            let zero_address = AccountId::from([0u8; 32]);
+
+            // bypass the check in propose_transfer_ownership
+            contract.proposed_owner = Some(zero_address);
+
+            set_sender(zero_address);
+            assert_eq!(contract.accept_ownership(), Err(Error::InvalidParams));
+            assert_eq!(contract.owner, accounts.alice);
+        }
+
+        /// Get the highest base payment the treasury can sustain for one period
+        #[ink::test]
+        fn max_sustainable_base_payment() {
+            let (_, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            // Bob and Charlie have a final multiplier of 103 each
+            assert_eq!(contract.total_multiplier_weight(), 206);
+            let max_base_payment = contract.max_sustainable_base_payment();
+            assert_eq!(max_base_payment, 100_000_000 * 100 / 206);
+
+            assert!(contract.update_base_payment(max_base_payment).is_ok());
+            assert!(contract.get_total_debt_for_next_period() <= contract.get_contract_balance());
+
+            // one more unit would not be covered
+            assert!(206 * (max_base_payment + 1) / 100 > contract.get_contract_balance());
+        }
+
+        /// An approved delegate can claim on behalf of a beneficiary until the approval is revoked
+        #[ink::test]
+        fn claim_payment_by_delegate() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            advance_n_blocks(2);
+
+            // Django is not allowed to claim for Bob
+            set_sender(accounts.django);
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 100, None),
+                Err(Error::NotAllowedToClaim)
+            );
+
+            // Only beneficiaries can approve delegates
+            assert_eq!(
+                contract.approve_delegate(accounts.eve, true),
+                Err(Error::AccountNotFound)
+            );
+
+            set_sender(accounts.bob);
+            assert!(contract.approve_delegate(accounts.django, true).is_ok());
+            assert!(contract.is_delegate_approved(accounts.bob, accounts.django));
+            match recorded_events().last() {
+                Some(Event::DelegateApprovalChanged(DelegateApprovalChanged {
+                    beneficiary,
+                    delegate,
+                    approved,
+                })) => {
+                    assert_eq!(*beneficiary, accounts.bob);
+                    assert_eq!(*delegate, accounts.django);
+                    assert!(*approved);
+                }
+                _ => panic!("DelegateApprovalChanged event not emitted"),
+            }
+
+            // The delegate claims, the funds go to Bob
+            let bob_balance = get_balance(accounts.bob);
+            let django_balance = get_balance(accounts.django);
+            set_sender(accounts.django);
+            assert!(contract.claim_payment(accounts.bob, 100, None).is_ok());
+            assert_eq!(get_balance(accounts.bob), bob_balance + 100);
+            assert_eq!(get_balance(accounts.django), django_balance);
+
+            // The approval is only for Bob
+            assert_eq!(
+                contract.claim_payment(accounts.charlie, 100, None),
+                Err(Error::NotAllowedToClaim)
+            );
+
+            set_sender(accounts.bob);
+            assert!(contract.approve_delegate(accounts.django, false).is_ok());
+            assert!(!contract.is_delegate_approved(accounts.bob, accounts.django));
+
+            set_sender(accounts.django);
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 100, None),
+                Err(Error::NotAllowedToClaim)
+            );
+        }
+
+        /// A partial claim is not counted twice in the debt with unclaimed for the next period
+        #[ink::test]
+        fn total_debt_with_unclaimed_for_next_period_after_partial_claim() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            advance_n_blocks(2);
+
+            assert!(contract.claim_payment(accounts.bob, 100, None).is_ok());
+            let bob_unclaimed = contract
+                .get_beneficiary(accounts.bob)
+                .unwrap()
+                .unclaimed_payments;
+            assert_eq!(bob_unclaimed, 1030 - 100);
+
+            // Bob: the remaining unclaimed plus one more period
+            // Charlie: the current period plus one more period
+            assert_eq!(
+                contract.get_total_debt_with_unclaimed_for_next_period(),
+                bob_unclaimed + 1030 + 1030 * 2
+            );
+        }
+
+        /// The total paid is recorded at the end of each period with claims
+        #[ink::test]
+        fn total_paid_at_period() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            advance_n_blocks(2);
+
+            assert!(contract.claim_payment(accounts.bob, 100, None).is_ok());
+            assert!(contract.claim_payment(accounts.charlie, 200, None).is_ok());
+            let period_block = contract.get_current_period_initial_block();
+
+            // Not recorded until the period rolls over
+            assert_eq!(contract.get_total_paid_at_period(period_block), None);
+
+            advance_n_blocks(2);
+            assert!(contract.claim_payment(accounts.bob, 300, None).is_ok());
+
+            assert_eq!(contract.get_total_paid_at_period(period_block), Some(300));
+            assert_eq!(contract.get_stats().total_paid, 600);
+            assert_eq!(
+                contract.get_total_paid_at_period(contract.get_current_period_initial_block()),
+                None
+            );
+        }
+
+        /// Creating the base multipliers errors instead of panicking when the multiplier id overflows
+        #[ink::test]
+        fn create_base_multipliers_id_overflow() {
+            let (_, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            // No constructor call can get this close to the limit, so the id is set directly
+            contract.next_multiplier_id = MultiplierId::MAX;
+
+            assert_eq!(
+                contract._create_base_multipliers(vec!["Seniority".to_string()]),
+                Err(Error::MultiplierIdOverflow)
+            );
+        }
+
+        /// The preview of a base payment change scales the amounts of every beneficiary
+        #[ink::test]
+        fn preview_base_payment_change() {
+            let (accounts, contract) = create_accounts_and_contract(100_000_000u128);
+
+            let preview = contract.preview_base_payment_change(2000);
+            assert_eq!(
+                preview,
+                vec![(accounts.bob, 1030, 2060), (accounts.charlie, 1030, 2060)]
+            );
+
+            // Nothing is changed
+            assert_eq!(contract.get_base_payment(), 1000);
+        }
+
+        /// The preview of a huge base payment saturates instead of overflowing
+        #[ink::test]
+        fn preview_base_payment_change_saturates() {
+            let (accounts, contract) = create_accounts_and_contract(100_000_000u128);
+
+            let preview = contract.preview_base_payment_change(Balance::MAX);
+            assert_eq!(
+                preview,
+                vec![
+                    (accounts.bob, 1030, Balance::MAX / 100),
+                    (accounts.charlie, 1030, Balance::MAX / 100)
+                ]
+            );
+        }
+
+        /// Claim a number of periods instead of an amount
+        #[ink::test]
+        fn claim_periods() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            advance_n_blocks(6);
+
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030 * 3));
+            assert_eq!(contract.claim_periods(accounts.bob, 2, true), Ok(1030 * 2));
+
+            // The period left stays claimable
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+
+            // The periods are capped at the accrued ones and the unclaimed payments are kept unless requested
+            advance_n_blocks(2);
+            assert_eq!(contract.claim_periods(accounts.bob, 5, false), Ok(1030));
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+
+            advance_n_blocks(2);
+            assert_eq!(contract.claim_periods(accounts.bob, 5, true), Ok(1030 * 2));
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(0));
+
+            // Zero periods are rejected instead of claiming only the unclaimed payments
+            advance_n_blocks(2);
+            assert_eq!(
+                contract.claim_periods(accounts.bob, 0, true),
+                Err(Error::InvalidParams)
+            );
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+
+            assert_eq!(
+                contract.claim_periods(accounts.django, 1, true),
+                Err(Error::AccountNotFound)
+            );
+        }
+
+        /// Freezing the whole contract blocks the mutating messages until it is unfrozen
+        #[ink::test]
+        fn freeze_all() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            advance_n_blocks(2);
+
+            // Only the owner can freeze
+            set_sender(accounts.bob);
+            assert_eq!(contract.freeze_all(), Err(Error::NotOwner));
+
+            set_sender(accounts.alice);
+            assert!(contract.freeze_all().is_ok());
+            assert!(contract.is_contract_frozen());
+            match recorded_events().last() {
+                Some(Event::ContractFreezeChanged(ContractFreezeChanged { frozen })) => {
+                    assert!(*frozen);
+                }
+                _ => panic!("ContractFreezeChanged event not emitted"),
+            }
+
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 100, None),
+                Err(Error::ContractFrozen)
+            );
+            assert_eq!(
+                contract.add_beneficiary(accounts.django, vec![(0, 100), (1, 3)]),
+                Err(Error::ContractFrozen)
+            );
+            assert_eq!(contract.emergency_withdraw(), Err(Error::ContractFrozen));
+            assert_eq!(contract.pause(None), Err(Error::ContractFrozen));
+
+            // Reads keep working
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+
+            assert!(contract.unfreeze_all().is_ok());
+            assert!(!contract.is_contract_frozen());
+            assert!(contract.claim_payment(accounts.bob, 100, None).is_ok());
+            assert!(contract
+                .add_beneficiary(accounts.django, vec![(0, 100), (1, 3)])
+                .is_ok());
+        }
+
+        /// The periodicity is read as the stored period length
+        #[ink::test]
+        fn get_periodicity() {
+            let (_, mut contract) = create_accounts_and_contract(100_000_000u128);
+            let periodicity: u32 = contract.get_periodicity();
+            assert_eq!(periodicity, 2);
+
+            assert!(contract.update_periodicity(5, false).is_ok());
+            assert_eq!(contract.get_periodicity(), 5);
+        }
+
+        /// The export of the multipliers has the names and the deactivation states
+        #[ink::test]
+        fn export_multipliers() {
+            let (_, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            assert!(contract.deactivate_multiplier(1, false).is_ok());
+            let valid_until_block = contract.get_deletable_at(1).unwrap();
+
+            assert_eq!(
+                contract.export_multipliers(),
+                vec![
+                    (
+                        0,
+                        BaseMultiplier {
+                            name: "Seniority".to_string(),
+                            valid_until_block: None,
+                        }
+                    ),
+                    (
+                        1,
+                        BaseMultiplier {
+                            name: "Performance".to_string(),
+                            valid_until_block: Some(valid_until_block),
+                        }
+                    ),
+                ]
+            );
+        }
+
+        /// Re-anchoring moves the initial block to the current period start when the periodicity changes
+        #[ink::test]
+        fn update_periodicity_re_anchor() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            advance_n_blocks(2);
+            contract.claim_payment(accounts.bob, 0, None).unwrap();
+            contract.claim_payment(accounts.charlie, 0, None).unwrap();
+            advance_n_blocks(1);
+
+            contract.update_periodicity(5, true).unwrap();
+            assert_eq!(contract.get_initial_block(), 2);
+            assert_eq!(contract.get_current_period_initial_block(), 2);
+            assert_eq!(contract.get_next_block_period(), 2 + 5);
+
+            // The accrued period is kept and the next one ends at the new boundary
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+            advance_n_blocks(3);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030));
+            advance_n_blocks(1);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(2060));
+        }
+
+        /// Get the statuses of active, deactivated, deleted and unknown multipliers
+        #[ink::test]
+        fn get_multiplier_statuses() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            contract.deactivate_multiplier(1, false).unwrap();
+            advance_n_blocks(4);
+            for account_id in [accounts.bob, accounts.charlie] {
+                contract.claim_payment(account_id, 0, None).unwrap();
+            }
+            contract.delete_unused_multiplier(1).unwrap();
+
+            contract.add_base_multiplier("Bonus".to_string()).unwrap();
+            contract.deactivate_multiplier(2, false).unwrap();
+            let valid_until_block = contract.get_deletable_at(2).unwrap();
+
+            assert_eq!(
+                contract.get_multiplier_statuses(vec![0, 1, 2, 7]),
+                vec![
+                    (0, MultiplierStatus::Active),
+                    (1, MultiplierStatus::Deleted),
+                    (2, MultiplierStatus::Deactivated(valid_until_block)),
+                    (7, MultiplierStatus::NotFound),
+                ]
+            );
+        }
+
+        /// Non zero claims are only allowed in the claim window of the period
+        #[ink::test]
+        fn claim_window_blocks() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            assert!(contract.update_periodicity(10, false).is_ok());
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                contract.set_claim_window_blocks(Some(3)),
+                Err(Error::NotOwner)
+            );
+            set_sender(accounts.alice);
+            assert!(contract.set_claim_window_blocks(Some(3)).is_ok());
+
+            // Inside the window
+            advance_n_blocks(13);
+            assert!(contract.claim_payment(accounts.bob, 100, None).is_ok());
+
+            // Outside the window
+            advance_n_blocks(1);
+            assert_eq!(
+                contract.claim_payment(accounts.charlie, 100, None),
+                Err(Error::OutsideClaimWindow)
+            );
+
+            // Settling is still allowed
+            assert!(contract.claim_payment(accounts.charlie, 0, None).is_ok());
+
+            assert!(contract.set_claim_window_blocks(None).is_ok());
+            assert!(contract.claim_payment(accounts.charlie, 100, None).is_ok());
+        }
+
+        /// The next period of a beneficiary uses its own periodicity
+        #[ink::test]
+        fn get_beneficiary_next_period() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            assert!(contract
+                .set_beneficiary_periodicity(accounts.bob, Some(5))
+                .is_ok());
+
+            advance_n_blocks(3);
+            assert_eq!(contract.get_beneficiary_next_period(accounts.bob), Ok(5));
+            assert_eq!(
+                contract.get_beneficiary_next_period(accounts.charlie),
+                Ok(4)
+            );
+            assert_eq!(contract.get_next_block_period(), 4);
+
+            advance_n_blocks(2);
+            assert_eq!(contract.get_beneficiary_next_period(accounts.bob), Ok(10));
+
+            assert_eq!(
+                contract.get_beneficiary_next_period(accounts.django),
+                Err(Error::AccountNotFound)
+            );
+        }
+
+        /// A failed claim does not prune the multipliers of the beneficiary
+        #[ink::test]
+        fn claim_payment_failed_keeps_multipliers() {
+            let (accounts, mut contract) = create_accounts_and_contract(1500u128);
+
+            contract.deactivate_multiplier(1, true).unwrap();
+            advance_n_blocks(4);
+
+            let multipliers = contract.get_beneficiary(accounts.bob).unwrap().multipliers;
+            assert_eq!(multipliers.len(), 2);
+
+            // The treasury can not cover the claim
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 2000, None),
+                Err(Error::NotEnoughBalanceInTreasury)
+            );
+            assert_eq!(
+                contract.get_beneficiary(accounts.bob).unwrap().multipliers,
+                multipliers
+            );
+
+            // A successful claim prunes the expired multiplier
+            assert!(contract.claim_payment(accounts.bob, 0, None).is_ok());
+            assert_eq!(
+                contract
+                    .get_beneficiary(accounts.bob)
+                    .unwrap()
+                    .multipliers
+                    .len(),
+                1
+            );
+        }
+
+        /// Claim the maximum the treasury can pay and keep the rest as unclaimed
+        #[ink::test]
+        fn claim_max() {
+            let (accounts, mut contract) = create_accounts_and_contract(1500u128);
+            advance_n_blocks(4);
+
+            let bob_balance = get_balance(accounts.bob);
+            assert_eq!(contract.claim_max(accounts.bob), Ok(1500));
+            assert_eq!(get_balance(accounts.bob), bob_balance + 1500);
+            assert_eq!(
+                contract
+                    .get_beneficiary(accounts.bob)
+                    .unwrap()
+                    .unclaimed_payments,
+                1030 * 2 - 1500
+            );
+
+            // Nothing left in the treasury, only settles
+            assert_eq!(contract.claim_max(accounts.charlie), Ok(0));
+            assert_eq!(
+                contract
+                    .get_beneficiary(accounts.charlie)
+                    .unwrap()
+                    .unclaimed_payments,
+                1030 * 2
+            );
+
+            assert_eq!(
+                contract.claim_max(accounts.django),
+                Err(Error::AccountNotFound)
+            );
+        }
+
+        /// A beneficiary can keep a deactivated multiplier until it expires
+        #[ink::test]
+        fn update_beneficiary_with_deactivated_multiplier() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            contract.deactivate_multiplier(1, false).unwrap();
+            let valid_until_block = contract.get_deletable_at(1).unwrap();
+
+            assert!(contract
+                .update_beneficiary(accounts.bob, vec![(0, 100), (1, 5)])
+                .is_ok());
+
+            // New beneficiaries can not get a deactivated multiplier
+            assert_eq!(
+                contract.add_beneficiary(accounts.django, vec![(0, 100), (1, 5)]),
+                Err(Error::MultiplierAlreadyDeactivated)
+            );
+
+            // Existing beneficiaries can not get a deactivated multiplier they did not have
+            contract.add_base_multiplier("Bonus".to_string()).unwrap();
+            contract.deactivate_multiplier(2, false).unwrap();
+            assert_eq!(
+                contract.update_beneficiary(accounts.bob, vec![(0, 100), (1, 5), (2, 500)]),
+                Err(Error::MultiplierAlreadyDeactivated)
+            );
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(0));
+
+            advance_n_blocks(valid_until_block);
+            assert_eq!(
+                contract.update_beneficiary(accounts.bob, vec![(0, 100), (1, 6)]),
+                Err(Error::MultiplierExpired)
+            );
+            assert!(contract
+                .update_beneficiary(accounts.bob, vec![(0, 100)])
+                .is_ok());
+        }
+
+        /// The reconciliation shows a surplus or a shortfall of the treasury
+        #[ink::test]
+        fn treasury_reconciliation() {
+            let (_, contract) = create_accounts_and_contract(3000u128);
+            advance_n_blocks(2);
+
+            assert_eq!(
+                contract.treasury_reconciliation(),
+                (3000, 1030 * 2, 3000 - 1030 * 2)
+            );
+
+            advance_n_blocks(2);
+            assert_eq!(
+                contract.treasury_reconciliation(),
+                (3000, 1030 * 4, 3000 - 1030 * 4)
+            );
+            assert!(contract.treasury_reconciliation().2 < 0);
+        }
+
+        /// The projected liability adds the given periods to the debts up-to-date
+        #[ink::test]
+        fn projected_liability() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            advance_n_blocks(2);
+            assert!(contract.claim_payment(accounts.bob, 100, None).is_ok());
+
+            assert_eq!(
+                contract.projected_liability(3),
+                contract.get_total_debts() + 3 * contract.get_total_debt_for_next_period()
+            );
+            assert_eq!(
+                contract.projected_liability(1),
+                contract.get_total_debt_with_unclaimed_for_next_period()
+            );
+            assert_eq!(contract.projected_liability(0), contract.get_total_debts());
+        }
+
+        /// A beneficiary claims everything without passing the account and the amount
+        #[ink::test]
+        fn claim_all() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            advance_n_blocks(4);
+
+            set_sender(accounts.bob);
+            let bob_balance = get_balance(accounts.bob);
+            assert_eq!(contract.claim_all(), Ok(1030 * 2));
+            assert_eq!(get_balance(accounts.bob), bob_balance + 1030 * 2);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(0));
+
+            set_sender(accounts.django);
+            assert_eq!(contract.claim_all(), Err(Error::AccountNotFound));
+
+            set_sender(accounts.alice);
+            assert!(contract.pause(None).is_ok());
+            set_sender(accounts.charlie);
+            assert_eq!(contract.claim_all(), Err(Error::ContractIsPaused));
+        }
+
+        /// The funding check covers every initial beneficiary, whatever the constructor and the multipliers
+        #[ink::test]
+        fn create_contract_require_funded_all_beneficiaries() {
+            let accounts = default_accounts();
+            let beneficiaries = || {
+                vec![
+                    InitialBeneficiary {
+                        account_id: accounts.bob,
+                        multipliers: vec![],
+                    },
+                    InitialBeneficiary {
+                        account_id: accounts.charlie,
+                        multipliers: vec![],
+                    },
+                ]
+            };
+
+            // without multipliers each beneficiary is paid the full base payment
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1999);
+            let res = OpenPayroll::new(
+                2,
+                1000,
+                vec![],
+                beneficiaries(),
+                true,
+                None,
+                EmptyPolicy::PayFullBase,
+                false,
+            );
+            assert!(matches!(res, Err(Error::NotEnoughBalanceInTreasury)));
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+            let res = OpenPayroll::new(
+                2,
+                1000,
+                vec![],
+                beneficiaries(),
+                true,
+                None,
+                EmptyPolicy::PayFullBase,
+                false,
+            );
+            assert!(res.is_ok());
+
+            // the flag is also checked with the multipliers given by name
+            let res = OpenPayroll::new_with_named_multipliers(
+                2,
+                1000,
+                vec!["Seniority".to_string()],
+                vec![NamedInitialBeneficiary {
+                    account_id: accounts.bob,
+                    multipliers: vec![("Seniority".to_string(), 201)],
+                }],
+                true,
+                None,
+                EmptyPolicy::PayFraction,
+                false,
+            );
+            assert!(matches!(res, Err(Error::NotEnoughBalanceInTreasury)));
+        }
+
+        /// With the payout allowlist enabled only allowlisted accounts receive payments
+        #[ink::test]
+        fn claim_payment_with_payout_allowlist() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            advance_n_blocks(2);
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                contract.set_payout_allowlist_enabled(true),
+                Err(Error::NotOwner)
+            );
+
+            set_sender(accounts.alice);
+            assert!(contract.set_payout_allowlist_enabled(true).is_ok());
+            assert!(contract.set_payout_allowed(accounts.bob, true).is_ok());
+            assert!(contract.is_payout_allowed(accounts.bob));
+            assert!(!contract.is_payout_allowed(accounts.charlie));
+
+            assert!(contract.claim_payment(accounts.bob, 100, None).is_ok());
+            assert_eq!(
+                contract.claim_payment(accounts.charlie, 100, None),
+                Err(Error::RecipientNotAllowed)
+            );
+
+            // Settling transfers nothing, so it is allowed
+            assert!(contract.claim_payment(accounts.charlie, 0, None).is_ok());
+
+            assert!(contract.set_payout_allowed(accounts.bob, false).is_ok());
+            assert_eq!(
+                contract.claim_payment(accounts.bob, 100, None),
+                Err(Error::RecipientNotAllowed)
+            );
+
+            assert!(contract.set_payout_allowlist_enabled(false).is_ok());
+            assert!(contract.claim_payment(accounts.charlie, 100, None).is_ok());
+        }
+
+        /// The payout of one period does not depend on the elapsed periods
+        #[ink::test]
+        fn get_period_payout() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+
+            // (100 + 3) * 1000 / 100
+            assert_eq!(contract.get_period_payout(accounts.bob), Ok(1030));
+
+            advance_n_blocks(6);
+            assert_eq!(contract.get_amount_to_claim(accounts.bob), Some(1030 * 3));
+            assert_eq!(contract.get_period_payout(accounts.bob), Ok(1030));
+
+            assert!(contract.claim_payment(accounts.bob, 100, None).is_ok());
+            assert_eq!(contract.get_period_payout(accounts.bob), Ok(1030));
+
+            assert_eq!(
+                contract.get_period_payout(accounts.django),
+                Err(Error::AccountNotFound)
+            );
+        }
+
+        /// A beneficiary with a shorter periodicity is counted once per contract period
+        #[ink::test]
+        fn claims_in_period_with_shorter_periodicity_override() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            assert!(contract
+                .set_beneficiary_periodicity(accounts.bob, Some(1))
+                .is_ok());
+
+            advance_n_blocks(2);
+            assert!(contract.claim_payment(accounts.bob, 0, None).is_ok());
+            advance_n_blocks(1);
+            assert!(contract.claim_payment(accounts.bob, 0, None).is_ok());
+
+            // Charlie has not claimed in the contract period started at block 2
+            assert_eq!(contract.claims_status(), (2, 1, 2));
+            assert!(!contract.can_update_schedule());
+            assert_eq!(
+                contract.update_base_payment(2000),
+                Err(Error::NotAllClaimedInPeriod)
+            );
+
+            assert!(contract.claim_payment(accounts.charlie, 0, None).is_ok());
+            assert!(contract.can_update_schedule());
+        }
+
+        /// A beneficiary with a longer periodicity is counted in every contract period it claims
+        #[ink::test]
+        fn claims_in_period_with_longer_periodicity_override() {
+            let (accounts, mut contract) = create_accounts_and_contract(100_000_000u128);
+            assert!(contract
+                .set_beneficiary_periodicity(accounts.bob, Some(4))
+                .is_ok());
+
+            advance_n_blocks(6);
+            assert!(contract.claim_payment(accounts.bob, 0, None).is_ok());
+            assert!(contract.claim_payment(accounts.bob, 0, None).is_ok());
+            assert!(!contract.can_update_schedule());
+
+            assert!(contract.claim_payment(accounts.charlie, 0, None).is_ok());
+            assert!(contract.can_update_schedule());
+            assert!(contract.update_base_payment(2000).is_ok());
+        }
     }
 }